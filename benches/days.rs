@@ -0,0 +1,67 @@
+// One benchmark group per day, with parsing and each part measured on their
+// own against the real inputs in inputs/. Days without an input are skipped,
+// as are parts that aren't implemented or don't come up with an answer.
+//
+//     cargo bench
+//     cargo bench -- day15
+
+use std::time::{Duration, Instant};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc20::{get_day, Answer, InputSource};
+use aoc20::runner::read_input;
+use aoc20::years::{years, Part};
+
+// Criterion's default of 100 samples would take minutes on the slow days,
+// anything this slow gets the minimum instead.
+const SLOW: Duration = Duration::from_millis(100);
+
+fn part_name(part: Part) -> &'static str {
+    match part {
+        Part::One => "part_one",
+        Part::Two => "part_two"
+    }
+}
+
+fn bench_days(c: &mut Criterion) {
+    for year in years() {
+        for day in (1..=25).filter_map(|day| get_day(year, &day.to_string())) {
+            let input = match read_input(&day, &InputSource::default()) {
+                Ok(input) => input,
+                Err(_) => {
+                    eprintln!("Skipping {} day {}, no input", year, day.day);
+                    continue;
+                }
+            };
+            let input = input.trim();
+
+            let parsed = match (day.parse)(input) {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    eprintln!("Skipping {} day {}: {}", year, day.day, error);
+                    continue;
+                }
+            };
+
+            let mut group = c.benchmark_group(format!("{}/day{:02}", year, day.day.parse::<u32>().unwrap_or(0)));
+            group.bench_function("parse", |b| b.iter(|| (day.parse)(black_box(input))));
+
+            for part in [Part::One, Part::Two].iter().copied() {
+                let start = Instant::now();
+                match parsed.part(part) {
+                    Ok(Answer::NotImplemented) | Err(_) => continue,
+                    Ok(_) => {}
+                }
+                group.sample_size(if start.elapsed() > SLOW { 10 } else { 100 });
+
+                group.bench_function(part_name(part), |b| b.iter(|| parsed.part(black_box(part))));
+            }
+
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
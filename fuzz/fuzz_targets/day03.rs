@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::three::Puzzle;
+use aoc20::years::{solve_part_one, solve_part_two};
+
+fuzz_target!(|input: &str| {
+    let _ = solve_part_one::<Puzzle>(input);
+    let _ = solve_part_two::<Puzzle>(input);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::four::Passport;
+
+fuzz_target!(|input: &str| {
+    if let Ok(passport) = Passport::from_str(input) {
+        passport.part_one_valid();
+        passport.part_two_valid();
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::five::BoardingPass;
+
+fuzz_target!(|input: &str| {
+    if let Ok(pass) = input.parse::<BoardingPass>() {
+        pass.seat_id();
+    }
+});
@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::seven::BagSpec;
+
+fuzz_target!(|input: &str| {
+    let _ = BagSpec::from_str(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::fourteen::{Instruction, MemBitmask, ValueBitmask};
+
+// The same line parses both ways, one for each part's idea of what a mask is.
+fuzz_target!(|input: &str| {
+    let _ = input.parse::<Instruction<ValueBitmask>>();
+    let _ = input.parse::<Instruction<MemBitmask>>();
+});
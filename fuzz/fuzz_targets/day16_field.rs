@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::sixteen::Field;
+
+fuzz_target!(|input: &str| {
+    let _ = input.parse::<Field>();
+});
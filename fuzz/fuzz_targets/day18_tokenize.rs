@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc20::days::eighteen::{eval_expression_p1, eval_expression_p2, tokenize};
+
+fuzz_target!(|input: &str| {
+    let tokens = tokenize(input);
+    let _ = eval_expression_p1(&tokens);
+    let _ = eval_expression_p2(&tokens);
+});
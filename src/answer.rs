@@ -0,0 +1,106 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, self};
+
+// What a part comes up with. Keeping numbers as numbers lets the report line
+// them up, and knowing which parts aren't written yet lets it say so instead
+// of treating the placeholder text as an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    // For the odd answer that doesn't fit in an i64.
+    BigInt(i128),
+    Text(String),
+    // Puzzles whose answer is drawn in the output, one string per row.
+    Art(Vec<String>),
+    NotImplemented
+}
+
+impl Answer {
+    pub fn is_implemented(&self) -> bool {
+        *self != Answer::NotImplemented
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Answer::Int(_) | Answer::BigInt(_))
+    }
+
+    // The answer on one line, for recording and comparing. Art rows are
+    // separated by a literal \n.
+    pub fn to_line(&self) -> String {
+        match self {
+            Answer::Art(rows) => rows.join("\\n"),
+            answer => answer.to_string()
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Passing the formatter through keeps width and alignment working.
+        match self {
+            Answer::Int(n) => Display::fmt(n, f),
+            Answer::BigInt(n) => Display::fmt(n, f),
+            Answer::Text(text) => f.pad(text),
+            Answer::Art(rows) => f.pad(&rows.join("\n")),
+            Answer::NotImplemented => f.pad("Not implemented")
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ( $( $int:ty ),* ) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Answer {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        Err(_) => Answer::BigInt(n as i128)
+                    }
+                }
+            }
+        )*
+    }
+}
+
+answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Answer {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n)
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers_use_the_smallest_variant() {
+        assert_eq!(Answer::from(42usize), Answer::Int(42));
+        assert_eq!(Answer::from(-7isize), Answer::Int(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+    }
+
+    #[test]
+    fn art_lines() {
+        let art = Answer::Art(vec!["#..#".to_string(), "####".to_string()]);
+        assert_eq!(art.to_string(), "#..#\n####");
+        assert_eq!(art.to_line(), "#..#\\n####");
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt::{Display, Formatter, self};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::years::Day;
+use crate::runner::{year_path, PartReport};
+
+// Recorded answers live in answers/<year>/<day>.txt (see year_path), one line
+// per part:
+//
+//     1: 514579
+//     2: 241861950
+//
+// Either line can be missing if that part hasn't been recorded yet.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail(String),
+    New
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail(expected) => write!(f, "FAIL (expected {})", expected),
+            Check::New => write!(f, "NEW")
+        }
+    }
+}
+
+impl Check {
+    pub fn status(&self) -> &'static str {
+        match self {
+            Check::Pass => "PASS",
+            Check::Fail(_) => "FAIL",
+            Check::New => "NEW"
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>
+}
+
+fn answers_path(dir: &Path, day: &Day) -> PathBuf {
+    year_path(dir, day.year, &format!("{}.txt", day.day))
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Answers, AocError> {
+        let mut answers = Answers::default();
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            match line.split_once(": ") {
+                Some(("1", answer)) => answers.part_one = Some(answer.to_string()),
+                Some(("2", answer)) => answers.part_two = Some(answer.to_string()),
+                _ => return Err(AocError::Misc(format!("Bad answer line {}", line)))
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn load(dir: &Path, day: &Day) -> Result<Answers, AocError> {
+        match fs::read_to_string(answers_path(dir, day)) {
+            Ok(s) => Answers::parse(&s),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(error) => Err(error.into())
+        }
+    }
+
+    pub fn save(&self, dir: &Path, day: &Day) -> Result<(), AocError> {
+        let mut s = String::new();
+        if let Some(answer) = &self.part_one {
+            s.push_str(&format!("1: {}\n", answer));
+        }
+        if let Some(answer) = &self.part_two {
+            s.push_str(&format!("2: {}\n", answer));
+        }
+
+        let path = answers_path(dir, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, s)?;
+
+        Ok(())
+    }
+
+    // Fills in any unrecorded parts from the results, returning whether there
+    // was anything new. Recorded answers are never overwritten, if an answer
+    // really did change the old one has to be removed by hand. Parts that
+    // aren't implemented yet have nothing worth recording.
+    pub fn record(&mut self, part_one: &Option<PartReport>, part_two: &Option<PartReport>) -> bool {
+        let mut changed = false;
+
+        if let (None, Some(PartReport { result: Ok(answer), .. })) = (&self.part_one, part_one) {
+            if answer.is_implemented() {
+                self.part_one = Some(answer.to_line());
+                changed = true;
+            }
+        }
+        if let (None, Some(PartReport { result: Ok(answer), .. })) = (&self.part_two, part_two) {
+            if answer.is_implemented() {
+                self.part_two = Some(answer.to_line());
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+pub fn check(expected: &Option<String>, result: &Result<Answer, AocError>) -> Check {
+    match (expected, result) {
+        (None, _) => Check::New,
+        (Some(expected), Ok(answer)) if *expected == answer.to_line() => Check::Pass,
+        (Some(expected), _) => Check::Fail(expected.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("1: 514579\n2: 241861950\n").unwrap();
+        assert_eq!(answers.part_one, Some("514579".to_string()));
+        assert_eq!(answers.part_two, Some("241861950".to_string()));
+
+        let answers = Answers::parse("2: 7\n").unwrap();
+        assert_eq!(answers.part_one, None);
+
+        assert!(Answers::parse("3: 7").is_err());
+    }
+
+    #[test]
+    fn check_results() {
+        let expected = Some("42".to_string());
+        let fail = Check::Fail("42".to_string());
+        assert_eq!(check(&expected, &Ok(Answer::Int(42))), Check::Pass);
+        assert_eq!(check(&expected, &Ok(Answer::Int(41))), fail);
+        assert_eq!(check(&expected, &Err(AocError::Misc("oops".to_string()))), fail);
+        assert_eq!(check(&None, &Ok(Answer::Int(41))), Check::New);
+    }
+}
@@ -2,13 +2,149 @@ use std::error::Error;
 use std::fmt::{Display, Formatter, self};
 use std::io;
 use std::num;
+use std::ops::Range;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum AocError {
     Input(io::Error),
     BadInt(num::ParseIntError),
     BadFloat(num::ParseFloatError),
-    Misc(String)
+    Misc(String),
+    // A parser didn't like part of the input, with enough detail to point
+    // at it.
+    Parse(Box<Diagnostic>),
+    // Talking to the Advent of Code site failed, either the request itself
+    // or the site said no.
+    Http(String),
+    // The part was still running when the runner gave up on it.
+    Timeout(Duration),
+    // The part panicked. Location is file:line:column when the panic hook
+    // managed to catch it.
+    Panic { message: String, location: Option<String> }
+}
+
+impl AocError {
+    // The variant name, for output formats that want to report errors
+    // without parsing the Display text.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AocError::Input(_) => "Input",
+            AocError::BadInt(_) => "BadInt",
+            AocError::BadFloat(_) => "BadFloat",
+            AocError::Misc(_) => "Misc",
+            AocError::Parse(_) => "Parse",
+            AocError::Http(_) => "Http",
+            AocError::Timeout(_) => "Timeout",
+            AocError::Panic { .. } => "Panic"
+        }
+    }
+}
+
+// Where a parser gave up. Parsers only see their own piece of the input, so
+// they report a position within that and whoever split the input up moves it
+// out with within() until it's relative to the whole file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    // Filled in by the runner, parsers don't know which day they belong to.
+    pub day: Option<u8>,
+    // 1-based.
+    pub line: usize,
+    // Byte range of the bad input within text.
+    pub columns: Range<usize>,
+    // The whole line the bad input is on.
+    pub text: String
+}
+
+impl Diagnostic {
+    // The offending line with carets under the bad part of it.
+    pub fn render(&self) -> String {
+        let start = self.text[..self.columns.start].chars().count();
+        let width = self.text[self.columns.clone()].chars().count().max(1);
+        format!("{}\n{}{}", self.text, " ".repeat(start), "^".repeat(width))
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse error ")?;
+        if let Some(day) = self.day {
+            write!(f, "in day {} ", day)?;
+        }
+        write!(f, "at line {}, column {}: {}", self.line, self.columns.start + 1, self.message)
+    }
+}
+
+// Byte offset of inner within outer, if inner is a slice of it.
+fn offset_in(outer: &str, inner: &str) -> Option<usize> {
+    let outer_start = outer.as_ptr() as usize;
+    let inner_start = inner.as_ptr() as usize;
+    if inner_start >= outer_start && inner_start + inner.len() <= outer_start + outer.len() {
+        Some(inner_start - outer_start)
+    } else {
+        None
+    }
+}
+
+// The diagnostic for len bytes of source starting at offset.
+fn diagnose(message: String, source: &str, offset: usize, len: usize) -> Diagnostic {
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[offset..].find('\n').map_or(source.len(), |i| offset + i);
+    let start = offset - line_start;
+
+    Diagnostic {
+        message,
+        day: None,
+        line: source[..offset].matches('\n').count() + 1,
+        columns: start..(start + len).min(line_end - line_start),
+        text: source[line_start..line_end].to_string()
+    }
+}
+
+impl AocError {
+    // A parse error for bad, which should be a slice of source, the string
+    // being parsed. If it isn't the error points at all of source.
+    pub fn parse(message: &str, source: &str, bad: &str) -> AocError {
+        let (offset, len) = match offset_in(source, bad) {
+            Some(offset) => (offset, bad.len()),
+            None => (0, source.len())
+        };
+        AocError::Parse(Box::new(diagnose(message.to_string(), source, offset, len)))
+    }
+
+    // Moves a parse error from inner, the piece of input it was found in, out
+    // to outer, the string that piece was taken from.
+    pub fn within(self, outer: &str, inner: &str) -> AocError {
+        let (diagnostic, offset) = match (self, offset_in(outer, inner)) {
+            (AocError::Parse(diagnostic), Some(offset)) => (*diagnostic, offset),
+            (error, _) => return error
+        };
+
+        // The first line of inner might not start at the beginning of a line
+        // in outer, every other line does.
+        let moved = if diagnostic.line == 1 {
+            let start = offset + diagnostic.columns.start;
+            Diagnostic {
+                day: diagnostic.day,
+                ..diagnose(diagnostic.message, outer, start, diagnostic.columns.len())
+            }
+        } else {
+            Diagnostic {
+                line: diagnostic.line + outer[..offset].matches('\n').count(),
+                ..diagnostic
+            }
+        };
+
+        AocError::Parse(Box::new(moved))
+    }
+
+    pub fn in_day(self, day: u8) -> AocError {
+        match self {
+            AocError::Parse(diagnostic) => AocError::Parse(Box::new(Diagnostic { day: Some(day), ..*diagnostic })),
+            error => error
+        }
+    }
 }
 
 impl Display for AocError {
@@ -17,7 +153,19 @@ impl Display for AocError {
             AocError::Input(error) => write!(f, "Error opening input file: {}", error),
             AocError::BadInt(error) => write!(f, "Bad integer: {}", error),
             AocError::BadFloat(error) => write!(f, "Bad float: {}", error),
-            AocError::Misc(message) => write!(f, "Error running problem: {}", message)
+            AocError::Misc(message) => write!(f, "Error running problem: {}", message),
+            AocError::Parse(diagnostic) => write!(f, "{}", diagnostic),
+            AocError::Http(message) => write!(f, "HTTP error: {}", message),
+            AocError::Timeout(timeout) => write!(f, "TIMEOUT after {:.1?}", timeout),
+            AocError::Panic { message, location } => {
+                // Some panic messages quote the entire input, only the first
+                // line is worth putting in a report.
+                let message = message.lines().next().unwrap_or("");
+                match location {
+                    Some(location) => write!(f, "Panicked at {}: {}", location, message),
+                    None => write!(f, "Panicked: {}", message)
+                }
+            }
         }
     }
 }
@@ -50,3 +198,41 @@ impl From<num::ParseFloatError> for AocError {
         AocError::BadFloat(error)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn diagnostic(error: AocError) -> Diagnostic {
+        match error {
+            AocError::Parse(diagnostic) => *diagnostic,
+            error => panic!("Not a parse error: {}", error)
+        }
+    }
+
+    #[test]
+    fn errors_move_out_to_the_whole_input() {
+        let input = "nop +0\nacc +1\njmp x4\n";
+        let line = input.lines().nth(2).unwrap();
+        let error = AocError::parse("Bad argument", line, &line[4..]).within(input, line);
+
+        let diagnostic = diagnostic(error.in_day(8));
+        assert_eq!(diagnostic.day, Some(8));
+        assert_eq!(diagnostic.line, 3);
+        assert_eq!(diagnostic.columns, 4..6);
+        assert_eq!(diagnostic.render(), "jmp x4\n    ^^");
+    }
+
+    #[test]
+    fn multi_line_records_keep_their_lines() {
+        let input = "a:1 b:2\n\nc:3\nd4 e:5";
+        let record = input.split("\n\n").nth(1).unwrap();
+        let field = record.split_whitespace().nth(1).unwrap();
+        let error = AocError::parse("Bad record", record, field).within(input, record);
+
+        let diagnostic = diagnostic(error);
+        assert_eq!(diagnostic.line, 4);
+        assert_eq!(diagnostic.columns, 0..2);
+        assert_eq!(diagnostic.text, "d4 e:5");
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::{Instant, Duration};
+
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::years::{Day, Part, Parsed};
+use crate::runner::{call_parse, call_parsed, read_input, DayReport, InputSource, PartReport};
+use crate::selection::Parts;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            let zero = Duration::new(0, 0);
+            return Stats { min: zero, median: zero, mean: zero, stddev: zero };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>() / n as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt())
+        }
+    }
+}
+
+// The report's durations are the median of each phase's samples.
+pub struct BenchReport {
+    pub report: DayReport,
+    pub parse: Stats,
+    pub part_one: Option<Stats>,
+    pub part_two: Option<Stats>,
+    pub total: Stats,
+    pub iterations: usize
+}
+
+// A few untimed runs first so that the timed ones aren't paying for cold
+// caches and lazy_static regex compilation.
+fn warmup_iterations(iterations: usize) -> usize {
+    1 + iterations / 10
+}
+
+fn time_parse(day: &Day, input: &str, iterations: usize) -> Result<Vec<Duration>, AocError> {
+    for _ in 0..warmup_iterations(iterations) {
+        call_parse(day, input)?;
+    }
+
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        call_parse(day, input)?;
+        samples.push(start.elapsed());
+    }
+
+    Ok(samples)
+}
+
+// Every run of a part gets the same parsed input, parts that need to change
+// it work on their own copy.
+fn time_part(parsed: &dyn Parsed, part: Part, iterations: usize) -> (Result<Answer, AocError>, Vec<Duration>) {
+    for _ in 0..warmup_iterations(iterations) {
+        let _ = call_parsed(parsed, part);
+    }
+
+    let mut samples = Vec::with_capacity(iterations);
+    let mut result = call_parsed(parsed, part);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        result = call_parsed(parsed, part);
+        samples.push(start.elapsed());
+    }
+
+    (result, samples)
+}
+
+pub fn bench(day: &Day, iterations: usize, source: &InputSource, parts: Parts) -> Result<BenchReport, AocError> {
+    let buffer = read_input(day, source)?;
+    let input = buffer.trim();
+
+    let parse = time_parse(day, input, iterations)?;
+    let parsed = call_parse(day, input)?;
+
+    let part_one = if parts.one() { Some(time_part(&*parsed, Part::One, iterations)) } else { None };
+    let part_two = if parts.two() { Some(time_part(&*parsed, Part::Two, iterations)) } else { None };
+
+    // Each day total pairs up the nth run of each phase, which is as close as
+    // we can get to timing a whole day without running the phases back to back.
+    let totals: Vec<Duration> = (0..iterations)
+        .map(|i| {
+            parse[i] + part_one.iter()
+                .chain(part_two.iter())
+                .map(|(_, samples)| samples[i])
+                .sum::<Duration>()
+        })
+        .collect();
+
+    let stats_parse = Stats::from_samples(&parse);
+    let stats_one = part_one.as_ref().map(|(_, samples)| Stats::from_samples(samples));
+    let stats_two = part_two.as_ref().map(|(_, samples)| Stats::from_samples(samples));
+
+    let report = |part: Option<(Result<Answer, AocError>, Vec<Duration>)>, stats: &Option<Stats>| {
+        part.zip(*stats).map(|((result, _), stats)| PartReport::new(result, stats.median))
+    };
+
+    Ok(BenchReport {
+        report: DayReport {
+            parse: stats_parse.median,
+            // Benchmarks only report timings.
+            parse_memory: None,
+            part_one: report(part_one, &stats_one),
+            part_two: report(part_two, &stats_two)
+        },
+        parse: stats_parse,
+        part_one: stats_one,
+        part_two: stats_two,
+        total: Stats::from_samples(&totals),
+        iterations
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn stats_odd_samples() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean.as_micros(), 3000);
+    }
+
+    #[test]
+    fn stats_even_samples() {
+        let stats = Stats::from_samples(&millis(&[2, 4, 4, 4, 5, 5, 7, 9]));
+        assert_eq!(stats.median, Duration::from_millis(4) + Duration::from_micros(500));
+        assert_eq!(stats.mean.as_micros(), 5000);
+        assert_eq!(stats.stddev.as_micros(), 2000);
+    }
+}
@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use aoc20::AocError;
+use aoc20::history::{Comparison, History, Step};
+use aoc20::runner::format_duration;
+use aoc20::selection::Selection;
+use aoc20::years::{Part, DEFAULT_YEAR};
+
+use crate::cli::{error_message, parse_year, select_days, HISTORY_DIR, USAGE};
+
+struct Options {
+    year: u32,
+    selection: Option<Selection>,
+    baseline: Option<String>,
+    // Percent slower before a step gets flagged.
+    threshold: f64
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        selection: None,
+        baseline: None,
+        threshold: 10.
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--baseline" => {
+                let commit = args
+                    .next()
+                    .ok_or_else(|| "--baseline needs a commit".to_string())?;
+                options.baseline = Some(commit.to_string());
+            },
+            "--threshold" => {
+                let percent = args
+                    .next()
+                    .ok_or_else(|| "--threshold needs a percentage".to_string())?;
+                let percent: f64 = percent
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| format!("Bad threshold {}", percent))?;
+                if !percent.is_finite() || percent < 0. {
+                    return Err("--threshold can't be negative".to_string());
+                }
+                options.threshold = percent;
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.selection.is_none() => {
+                options.selection = Some(arg.parse().map_err(|error: AocError| error.to_string())?);
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    Ok(options)
+}
+
+fn step_label(step: Step) -> &'static str {
+    match step {
+        Step::Parse => "Parse",
+        Step::Part(Part::One) => "Part One",
+        Step::Part(Part::Two) => "Part Two"
+    }
+}
+
+fn print_comparison(comparison: &Comparison, threshold: f64) {
+    println!(
+        "  {:9} {:>8} ({}) -> {:>8} ({})  {:>+7.1}%{}",
+        format!("{}:", step_label(comparison.step)),
+        format_duration(comparison.baseline.duration),
+        comparison.baseline.commit,
+        format_duration(comparison.latest.duration),
+        comparison.latest.commit,
+        comparison.change(),
+        if comparison.slower_than(threshold) { "  SLOWER" } else { "" }
+    );
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let days = match select_days(options.year, &options.selection) {
+        Ok(days) => days,
+        Err(message) => {
+            println!("{}", message);
+            return 1;
+        }
+    };
+
+    let history = match History::load(Path::new(HISTORY_DIR), options.year) {
+        Ok(history) => history,
+        Err(error) => {
+            println!("{}", error_message(&error));
+            return 1;
+        }
+    };
+
+    let mut any_slower = false;
+    let mut any_compared = false;
+    for (day, parts) in days {
+        let comparisons: Vec<Comparison> = history
+            .compare(options.baseline.as_deref(), &[day.day.parse().unwrap_or(0)])
+            .into_iter()
+            .filter(|comparison| match comparison.step {
+                Step::Parse => true,
+                Step::Part(Part::One) => parts.one(),
+                Step::Part(Part::Two) => parts.two()
+            })
+            .collect();
+        if comparisons.is_empty() {
+            continue;
+        }
+
+        println!("\nDay {}: {}", day.day, day.name);
+        for comparison in &comparisons {
+            print_comparison(comparison, options.threshold);
+            any_slower |= comparison.slower_than(options.threshold);
+        }
+        any_compared = true;
+    }
+
+    if !any_compared {
+        println!("Nothing in the history to compare against");
+    }
+
+    if any_slower { 1 } else { 0 }
+}
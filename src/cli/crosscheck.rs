@@ -0,0 +1,111 @@
+use aoc20::AocError;
+use aoc20::differential::{check_part, Inputs, Outcome};
+use aoc20::runner::format_result;
+use aoc20::selection::Selection;
+use aoc20::years::{Part, DEFAULT_YEAR};
+
+use crate::cli::{parse_year, part_label, print_details, select_days, USAGE};
+
+struct Options {
+    year: u32,
+    selection: Option<Selection>,
+    seeds: u64,
+    size: usize
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, number: Option<&String>) -> Result<T, String> {
+    let number = number.ok_or_else(|| format!("{} needs a number", option))?;
+    number
+        .parse()
+        .map_err(|_| format!("Bad number {}", number))
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        selection: None,
+        seeds: 10,
+        size: 100
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--seeds" => options.seeds = parse_number("--seeds", args.next())?,
+            "--size" => options.size = parse_number("--size", args.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.selection.is_none() => {
+                options.selection = Some(arg.parse().map_err(|error: AocError| error.to_string())?);
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    if options.seeds == 0 || options.size == 0 {
+        return Err("--seeds and --size need to be at least 1".to_string());
+    }
+
+    Ok(options)
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let days = match select_days(options.year, &options.selection) {
+        Ok(days) => days,
+        Err(message) => {
+            println!("{}", message);
+            return 1;
+        }
+    };
+
+    let mut any_disagree = false;
+    for (day, parts) in days {
+        println!("\nDay {}: {}", day.day, day.name);
+
+        let parts = IntoIterator::into_iter([Part::One, Part::Two]).filter(|part| match part {
+            Part::One => parts.one(),
+            Part::Two => parts.two()
+        });
+        for part in parts {
+            for inputs in IntoIterator::into_iter([Inputs::Usual, Inputs::Tricky]) {
+                let label = match inputs {
+                    Inputs::Usual => part_label(part).to_string(),
+                    Inputs::Tricky => format!("{} on tricky inputs", part_label(part))
+                };
+
+                match check_part(&day, part, inputs, options.seeds, options.size) {
+                    // Most days don't have tricky inputs, no need to say so
+                    // every time.
+                    Outcome::Agreed(0) if inputs == Inputs::Tricky => {},
+                    Outcome::Agreed(0) => println!("  {}: Nothing to check", label),
+                    Outcome::Agreed(inputs) => println!("  {}: Agrees on {} inputs", label, inputs),
+                    Outcome::Disagreed(disagreement) => {
+                        any_disagree = true;
+                        println!(
+                            "  {}: Disagrees with the reference, seed {} at size {}",
+                            label, disagreement.seed, disagreement.size
+                        );
+                        println!("    Answer:    {}", format_result(&disagreement.answer));
+                        print_details(&disagreement.answer);
+                        println!("    Reference: {}", format_result(&disagreement.reference));
+                        print_details(&disagreement.reference);
+                        println!("    Smallest input found:");
+                        for line in disagreement.input.lines() {
+                            println!("      {}", line);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if any_disagree { 1 } else { 0 }
+}
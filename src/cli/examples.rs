@@ -0,0 +1,89 @@
+use aoc20::AocError;
+use aoc20::examples::run_example;
+use aoc20::runner::format_answer;
+use aoc20::selection::Selection;
+use aoc20::years::{Part, DEFAULT_YEAR};
+
+use crate::cli::{parse_year, part_label, print_details, select_days, USAGE};
+
+struct Options {
+    year: u32,
+    selection: Option<Selection>
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        selection: None
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.selection.is_none() => {
+                options.selection = Some(arg.parse().map_err(|error: AocError| error.to_string())?);
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    Ok(options)
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let days = match select_days(options.year, &options.selection) {
+        Ok(days) => days,
+        Err(message) => {
+            println!("{}", message);
+            return 1;
+        }
+    };
+
+    let mut passed = 0;
+    let mut total = 0;
+
+    for (day, parts) in days {
+        let examples = day.examples.iter().filter(|example| match example.part {
+            Part::One => parts.one(),
+            Part::Two => parts.two()
+        });
+
+        println!("\nDay {}: {}", day.day, day.name);
+
+        let mut any = false;
+        for example in examples {
+            any = true;
+            let result = run_example(&day, example);
+            let status = if result.passed() {
+                "PASS".to_string()
+            } else {
+                format!("FAIL (expected {})", example.expected)
+            };
+            println!("  {}: {} {}", part_label(example.part), format_answer(&result.result), status);
+            print_details(&result.result);
+
+            total += 1;
+            if result.passed() {
+                passed += 1;
+            }
+        }
+
+        if !any {
+            println!("  No examples");
+        }
+    }
+
+    println!("\n{}/{} examples passed", passed, total);
+
+    if passed == total { 0 } else { 1 }
+}
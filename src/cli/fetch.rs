@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use aoc20::client::{base_url, fetch_input, session_token, Client};
+use aoc20::selection::parse_day;
+use aoc20::years::DEFAULT_YEAR;
+
+use crate::cli::{error_message, parse_input_dir, parse_year, USAGE};
+
+struct Options {
+    year: u32,
+    day: Option<u32>,
+    input_dir: PathBuf,
+    base_url: String
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        day: None,
+        input_dir: PathBuf::from("inputs"),
+        base_url: base_url()
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--input-dir" => options.input_dir = parse_input_dir(args.next())?,
+            "--base-url" => {
+                let url = args
+                    .next()
+                    .ok_or_else(|| "--base-url needs a URL".to_string())?;
+                options.base_url = url.to_string();
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.day.is_none() => options.day = Some(parse_day(arg).map_err(|_| format!("Bad day {}", arg))?),
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    if options.day.is_none() {
+        return Err("fetch needs a day".to_string());
+    }
+
+    Ok(options)
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+    let day = options.day.unwrap();
+
+    let fetched = session_token().and_then(|session| {
+        let client = Client::new(&options.base_url, &session);
+        fetch_input(&client, &options.input_dir, options.year, day)
+    });
+
+    match fetched {
+        Ok(path) => {
+            println!("Saved {} day {} input to {}", options.year, day, path.display());
+            0
+        },
+        Err(error) => {
+            println!("{}", error_message(&error));
+            1
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::{self, Write};
+
+use aoc20::get_day;
+use aoc20::rng::Rng;
+use aoc20::selection::parse_day;
+use aoc20::years::DEFAULT_YEAR;
+
+use crate::cli::{parse_year, USAGE};
+
+struct Options {
+    year: u32,
+    day: Option<u32>,
+    seed: u64,
+    size: Option<usize>,
+    tricky: bool
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        day: None,
+        seed: 0,
+        size: None,
+        tricky: false
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--seed" => {
+                let seed = args
+                    .next()
+                    .ok_or_else(|| "--seed needs a number".to_string())?;
+                options.seed = seed
+                    .parse()
+                    .map_err(|_| format!("Bad seed {}", seed))?;
+            },
+            "--size" => {
+                let size = args
+                    .next()
+                    .ok_or_else(|| "--size needs a number".to_string())?;
+                options.size = Some(size.parse().map_err(|_| format!("Bad size {}", size))?);
+            },
+            "--tricky" => options.tricky = true,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.day.is_none() => options.day = Some(parse_day(arg).map_err(|_| format!("Bad day {}", arg))?),
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    if options.day.is_none() || options.size.is_none() {
+        return Err("gen needs a day and a --size".to_string());
+    }
+
+    Ok(options)
+}
+
+// Prints the input to stdout, so it can go straight to a file or to another
+// run's --input -.
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+    let (day, size) = (options.day.unwrap(), options.size.unwrap());
+
+    let generate = match get_day(options.year, &day.to_string()) {
+        Some(day) if options.tricky => day.generate_tricky,
+        Some(day) => day.generate,
+        None => {
+            println!("No implementation for {} day {}.", options.year, day);
+            return 1;
+        }
+    };
+
+    match generate(&mut Rng::new(options.seed), size) {
+        // Written rather than printed so that piping a big input into head
+        // doesn't end in a panic.
+        Some(input) => {
+            let _ = writeln!(io::stdout().lock(), "{}", input);
+            0
+        },
+        None => {
+            let kind = if options.tricky { "tricky generator" } else { "generator" };
+            println!("No {} for {} day {}", kind, options.year, day);
+            1
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use aoc20::{AocError, InputSource};
+use aoc20::runner::validate;
+use aoc20::selection::Selection;
+use aoc20::years::DEFAULT_YEAR;
+
+use crate::cli::{error_message, parse_input, parse_input_dir, parse_year, print_diagnostic, select_days, USAGE};
+
+struct Options {
+    year: u32,
+    selection: Option<Selection>,
+    input: InputSource
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        selection: None,
+        input: InputSource::default()
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--input" => options.input = parse_input(args.next())?,
+            "--input-dir" => options.input = InputSource::Dir(parse_input_dir(args.next())?),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.selection.is_none() => {
+                options.selection = Some(arg.parse().map_err(|error: AocError| error.to_string())?);
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    if let InputSource::File(_) | InputSource::Stdin = options.input {
+        match &options.selection {
+            Some(selection) if selection.0.len() == 1 => {},
+            _ => return Err("--input needs exactly one day".to_string())
+        }
+    }
+
+    Ok(options)
+}
+
+// Problems aren't necessarily parse errors, so they're described by where
+// they are rather than with the usual error message.
+fn print_problem(problem: &AocError) {
+    match problem {
+        AocError::Parse(diagnostic) => println!(
+            "  Line {}, column {}: {}",
+            diagnostic.line,
+            diagnostic.columns.start + 1,
+            diagnostic.message
+        ),
+        problem => println!("  {}", error_message(problem))
+    }
+    print_diagnostic(problem);
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let days = match select_days(options.year, &options.selection) {
+        Ok(days) => days,
+        Err(message) => {
+            println!("{}", message);
+            return 1;
+        }
+    };
+
+    let mut any_problems = false;
+    for (day, _) in days {
+        println!("\nDay {}: {}", day.day, day.name);
+
+        match validate(&day, &options.input) {
+            Err(error) => {
+                println!("  {}", error);
+                any_problems = true;
+            },
+            Ok(None) => println!("  No validator"),
+            Ok(Some(problems)) if problems.is_empty() => println!("  OK"),
+            Ok(Some(problems)) => {
+                for problem in &problems {
+                    print_problem(problem);
+                }
+                println!("  {} problem{}", problems.len(), if problems.len() == 1 { "" } else { "s" });
+                any_problems = true;
+            }
+        }
+    }
+
+    if any_problems { 1 } else { 0 }
+}
@@ -0,0 +1,161 @@
+// The binary's commands. Each one takes the arguments after its name and
+// returns the exit code for the process.
+
+use std::path::PathBuf;
+
+use aoc20::{get_day, Answer, AocError, Day, InputSource};
+use aoc20::selection::{Parts, Selection};
+use aoc20::years::{years, Part};
+
+pub mod compare;
+pub mod crosscheck;
+pub mod examples;
+pub mod fetch;
+pub mod gen;
+pub mod lint;
+pub mod new;
+pub mod run;
+pub mod submit;
+pub mod watch;
+
+pub const USAGE: &str = "Usage: aoc20 [--bench N] [--format text|json|csv] [--check] [--record]
+             [--input <file>|-] [--input-dir <dir>] [--jobs N] [--timeout SECS]
+             [--no-history] [--year YEAR] [days]
+       aoc20 compare [--year YEAR] [--baseline COMMIT] [--threshold PERCENT] [days]
+       aoc20 crosscheck [--year YEAR] [--seeds N] [--size N] [days]
+       aoc20 examples [--year YEAR] [days]
+       aoc20 fetch [--year YEAR] [--input-dir <dir>] [--base-url URL] <day>
+       aoc20 gen [--year YEAR] [--seed S] [--tricky] --size N <day>
+       aoc20 submit [--year YEAR] [--input <file>|-] [--input-dir <dir>]
+                    [--base-url URL] <day> <part>
+       aoc20 lint [--year YEAR] [--input <file>|-] [--input-dir <dir>] [days]
+       aoc20 new [--year YEAR] [--input-dir <dir>] <day> <name>
+       aoc20 watch [--year YEAR] [--input <file>] [--input-dir <dir>]
+                   [--interval SECS] <day>
+
+Days are a comma separated list of days or day ranges, each optionally
+followed by :1 or :2 to run only that part, e.g. 1-5,9,17:2
+
+--check compares each answer with the one in answers/<year>/<day>.txt, and
+--record saves any that aren't there yet. Both only work on the usual inputs.
+
+Runs on the usual inputs add their timings to history/<year>.txt. compare
+shows how each day's latest timings changed from the run before, or from the
+latest run at the --baseline commit, and flags anything more than --threshold
+percent slower (default 10). Timings are only compared with others taken the
+same way, --bench medians with --bench medians and --jobs runs with --jobs runs.
+
+lint checks inputs for anything the solutions rely on but don't check, for
+the days that have a validator.
+
+gen makes up an input for a day from the seed (default 0), N records long.
+What a record is depends on the day, a line for most of them, see each
+day's generator. --tricky makes one the puzzle allows but that breaks
+something the day's parts take for granted, for the days that have one.
+
+crosscheck runs each part and its reference version, the slow and obvious
+way of doing it, on inputs from the generator for seeds up to --seeds
+(default 10) and sizes doubling up to --size (default 100), and on tricky
+inputs too for the days that have them. Where they disagree it shows the
+smallest input it can find that still does.
+
+watch re-runs the day every time its input file changes, checking every
+--interval seconds (default 0.5). Point --input at an example saved to a file
+to iterate on that. Changes to the code need a rebuild and a new watch.
+
+fetch and submit read the session cookie from AOC_SESSION or ~/.config/aoc20/session,
+and the site's address from AOC_BASE_URL if --base-url isn't given.";
+
+pub const ANSWERS_DIR: &str = "answers";
+pub const SUBMISSIONS_DIR: &str = "submissions";
+pub const HISTORY_DIR: &str = "history";
+
+// Takes the argument after --year, if there was one.
+pub fn parse_year(year: Option<&String>) -> Result<u32, String> {
+    let year = year.ok_or_else(|| "--year needs a year".to_string())?;
+    let year = year
+        .parse()
+        .map_err(|_| format!("Bad year {}", year))?;
+    if !years().contains(&year) {
+        return Err(format!("No solutions for {}", year));
+    }
+    Ok(year)
+}
+
+// Takes the argument after --input, a file or - for stdin.
+pub fn parse_input(path: Option<&String>) -> Result<InputSource, String> {
+    let path = path.ok_or_else(|| "--input needs a file, or - for stdin".to_string())?;
+    if path == "-" {
+        Ok(InputSource::Stdin)
+    } else {
+        Ok(InputSource::File(PathBuf::from(path)))
+    }
+}
+
+// Takes the argument after --input-dir.
+pub fn parse_input_dir(dir: Option<&String>) -> Result<PathBuf, String> {
+    let dir = dir.ok_or_else(|| "--input-dir needs a directory".to_string())?;
+    Ok(PathBuf::from(dir))
+}
+
+pub fn select_days(year: u32, selection: &Option<Selection>) -> Result<Vec<(Day, Parts)>, String> {
+    let mut days = Vec::new();
+    match selection {
+        // A year in progress won't have every day yet, only complain about
+        // missing days when they were asked for by name.
+        None => {
+            for (day, parts) in Selection::all().0 {
+                if let Some(day) = get_day(year, &day.to_string()) {
+                    days.push((day, parts));
+                }
+            }
+        },
+        Some(selection) => {
+            for (day, parts) in &selection.0 {
+                match get_day(year, &day.to_string()) {
+                    Some(day) => days.push((day, *parts)),
+                    None => return Err(format!("No implementation for {} day {}.", year, day))
+                }
+            }
+        }
+    }
+    Ok(days)
+}
+
+pub fn part_label(part: Part) -> &'static str {
+    match part {
+        Part::One => "Part One",
+        Part::Two => "Part Two"
+    }
+}
+
+// Misc errors describe themselves as coming from a solution, which reads
+// oddly for the commands that don't run one.
+pub fn error_message(error: &AocError) -> String {
+    match error {
+        AocError::Misc(message) => message.clone(),
+        error => error.to_string()
+    }
+}
+
+// Anything that doesn't fit on a part's line of the report gets printed
+// under it: art answers, and the bad line of input for parse errors.
+pub fn print_details(result: &Result<Answer, AocError>) {
+    match result {
+        Ok(Answer::Art(rows)) => {
+            for row in rows {
+                println!("    {}", row);
+            }
+        },
+        Err(error) => print_diagnostic(error),
+        Ok(_) => {}
+    }
+}
+
+pub fn print_diagnostic(error: &AocError) {
+    if let AocError::Parse(diagnostic) = error {
+        for line in diagnostic.render().lines() {
+            println!("    {}", line);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use aoc20::scaffold::new_day;
+use aoc20::selection::parse_day;
+use aoc20::years::DEFAULT_YEAR;
+
+use crate::cli::{error_message, parse_input_dir, parse_year, USAGE};
+
+// New days are written into the source tree, so this has to be run from the
+// root of the repository.
+const SRC_DIR: &str = "src";
+
+struct Options {
+    year: u32,
+    day: Option<u32>,
+    name: Option<String>,
+    input_dir: PathBuf
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        day: None,
+        name: None,
+        input_dir: PathBuf::from("inputs")
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--input-dir" => options.input_dir = parse_input_dir(args.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.day.is_none() => options.day = Some(parse_day(arg).map_err(|_| format!("Bad day {}", arg))?),
+            _ if options.name.is_none() => options.name = Some(arg.to_string()),
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    if options.day.is_none() || options.name.is_none() {
+        return Err("new needs a day and a name".to_string());
+    }
+
+    Ok(options)
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+    let (day, name) = (options.day.unwrap(), options.name.unwrap());
+
+    match new_day(Path::new(SRC_DIR), &options.input_dir, options.year, day, &name) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+            0
+        },
+        Err(error) => {
+            println!("{}", error_message(&error));
+            1
+        }
+    }
+}
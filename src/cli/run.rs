@@ -0,0 +1,364 @@
+use std::path::Path;
+use std::time::{Instant, Duration};
+
+use aoc20::{Answer, AocError, Day, DayReport, InputSource};
+use aoc20::answers::{check, Answers, Check};
+use aoc20::bench::{bench, Stats};
+use aoc20::history::{current_commit, now, timings, History, Mode};
+use aoc20::memory::{format_bytes, Memory};
+use aoc20::output::{to_csv, to_json, Format, Totals};
+use aoc20::runner::{format_answer, format_duration, run_days, PartReport};
+use aoc20::selection::Selection;
+use aoc20::years::DEFAULT_YEAR;
+
+use crate::cli::{parse_input, parse_input_dir, parse_year, print_details, print_diagnostic, select_days, ANSWERS_DIR, HISTORY_DIR, USAGE};
+
+struct Options {
+    year: u32,
+    selection: Option<Selection>,
+    bench: Option<usize>,
+    format: Format,
+    check: bool,
+    record: bool,
+    input: InputSource,
+    jobs: usize,
+    timeout: Option<Duration>,
+    history: bool
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        selection: None,
+        bench: None,
+        format: Format::Text,
+        check: false,
+        record: false,
+        input: InputSource::default(),
+        jobs: 1,
+        timeout: None,
+        history: true
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bench" => {
+                let iterations = args
+                    .next()
+                    .ok_or_else(|| "--bench needs an iteration count".to_string())?;
+                let iterations = iterations
+                    .parse()
+                    .map_err(|_| format!("Bad iteration count {}", iterations))?;
+                if iterations == 0 {
+                    return Err("--bench needs at least one iteration".to_string());
+                }
+                options.bench = Some(iterations);
+            },
+            "--format" => {
+                let format = args
+                    .next()
+                    .ok_or_else(|| "--format needs a format".to_string())?;
+                options.format = format.parse().map_err(|error: AocError| error.to_string())?;
+            },
+            "--check" => options.check = true,
+            "--no-history" => options.history = false,
+            // Recording only fills in missing answers, so it checks the rest
+            // along the way.
+            "--record" => {
+                options.check = true;
+                options.record = true;
+            },
+            "--input" => options.input = parse_input(args.next())?,
+            "--input-dir" => options.input = InputSource::Dir(parse_input_dir(args.next())?),
+            "--year" => options.year = parse_year(args.next())?,
+            "--jobs" => {
+                let jobs = args
+                    .next()
+                    .ok_or_else(|| "--jobs needs a thread count".to_string())?;
+                options.jobs = jobs
+                    .parse()
+                    .map_err(|_| format!("Bad thread count {}", jobs))?;
+                if options.jobs == 0 {
+                    return Err("--jobs needs at least one thread".to_string());
+                }
+            },
+            "--timeout" => {
+                let seconds = args
+                    .next()
+                    .ok_or_else(|| "--timeout needs a number of seconds".to_string())?;
+                let seconds: f64 = seconds
+                    .parse()
+                    .map_err(|_| format!("Bad timeout {}", seconds))?;
+                if !seconds.is_finite() || seconds <= 0. {
+                    return Err("--timeout needs a positive number of seconds".to_string());
+                }
+                options.timeout = Some(Duration::from_secs_f64(seconds));
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.selection.is_none() => {
+                options.selection = Some(arg.parse().map_err(|error: AocError| error.to_string())?);
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    // Benchmarks running side by side would just be measuring each other.
+    if options.bench.is_some() && options.jobs > 1 {
+        return Err("--bench can't be combined with --jobs".to_string());
+    }
+
+    // Benchmarks run every iteration on this thread, there's nothing to
+    // abandon a slow one from.
+    if options.bench.is_some() && options.timeout.is_some() {
+        return Err("--bench can't be combined with --timeout".to_string());
+    }
+
+    // The answers on file are for the usual inputs. Checking another input
+    // against them would fail right answers, and recording would save wrong
+    // ones over the gaps.
+    if options.check && options.input != InputSource::default() {
+        return Err("--check and --record only work on the usual inputs, not --input or --input-dir".to_string());
+    }
+
+    // A single file can only be the input for a single day.
+    if let InputSource::File(_) | InputSource::Stdin = options.input {
+        match &options.selection {
+            Some(selection) if selection.0.len() == 1 => {},
+            _ => return Err("--input needs exactly one day".to_string())
+        }
+    }
+
+    Ok(options)
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "min {:>7}  median {:>7}  mean {:>7}  stddev {:>7}",
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.mean),
+        format_duration(stats.stddev)
+    )
+}
+
+fn format_check(check: &Option<Check>) -> String {
+    match check {
+        None => "".to_string(),
+        Some(check) => format!("  {}", check)
+    }
+}
+
+fn format_memory(memory: &Option<Memory>) -> String {
+    match memory {
+        None => "".to_string(),
+        Some(memory) => format!(
+            "  (peak {}, {} in {} allocations)",
+            format_bytes(memory.peak),
+            format_bytes(memory.allocated),
+            memory.allocations
+        )
+    }
+}
+
+fn print_part(label: &str, part: &PartReport, timing: &str) {
+    println!(
+        "  {}: {} {}{}{}",
+        label,
+        format_answer(&part.result),
+        timing,
+        format_memory(&part.memory),
+        format_check(&part.check)
+    );
+    print_details(&part.result);
+}
+
+pub fn print_run(report: &DayReport) {
+    println!("  Parse:    {:40} {}{}", "", format_duration(report.parse), format_memory(&report.parse_memory));
+    if let Some(part) = &report.part_one {
+        print_part("Part One", part, &format_duration(part.duration));
+    }
+    if let Some(part) = &report.part_two {
+        print_part("Part Two", part, &format_duration(part.duration));
+    }
+}
+
+fn print_bench(report: &DayReport, stats: &BenchStats) {
+    let (parse, part_one, part_two, total) = stats;
+    println!("  Parse:    {:40} {}", "", format_stats(parse));
+    if let (Some(part), Some(stats)) = (&report.part_one, part_one) {
+        print_part("Part One", part, &format_stats(stats));
+    }
+    if let (Some(part), Some(stats)) = (&report.part_two, part_two) {
+        print_part("Part Two", part, &format_stats(stats));
+    }
+    println!("  Total:    {:40} {}", "", format_stats(total));
+}
+
+// Only runs on the usual inputs go in the history, timings for some other
+// input wouldn't be comparable.
+fn record_history(year: u32, input: &InputSource, mode: Mode, reports: &[(Day, Result<DayReport, AocError>)]) -> Result<(), AocError> {
+    if let InputSource::File(_) | InputSource::Stdin = input {
+        return Ok(());
+    }
+
+    let time = now();
+    let commit = current_commit();
+    let timings: Vec<_> = reports
+        .iter()
+        .filter_map(|(day, report)| Some((day.day.parse().ok()?, report.as_ref().ok()?)))
+        .flat_map(|(day, report)| timings(day, report, time, &commit, mode))
+        .collect();
+
+    if timings.is_empty() {
+        return Ok(());
+    }
+    History::append(Path::new(HISTORY_DIR), year, &timings)
+}
+
+fn check_answers(day: &Day, report: &mut DayReport, record: bool) -> Result<(), AocError> {
+    let dir = Path::new(ANSWERS_DIR);
+    let mut answers = Answers::load(dir, day)?;
+
+    // There's nothing to check for parts that haven't been written yet.
+    let implemented = |part: &PartReport| !matches!(part.result, Ok(Answer::NotImplemented));
+
+    if let Some(part) = report.part_one.as_mut().filter(|part| implemented(part)) {
+        part.check = Some(check(&answers.part_one, &part.result));
+    }
+    if let Some(part) = report.part_two.as_mut().filter(|part| implemented(part)) {
+        part.check = Some(check(&answers.part_two, &part.result));
+    }
+
+    if record && answers.record(&report.part_one, &report.part_two) {
+        answers.save(dir, day)?;
+    }
+
+    Ok(())
+}
+
+type BenchStats = (Stats, Option<Stats>, Option<Stats>, Stats);
+
+fn is_failure(check: &Option<Check>) -> bool {
+    matches!(check, Some(Check::Fail(_)))
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let days = match select_days(options.year, &options.selection) {
+        Ok(days) => days,
+        Err(message) => {
+            println!("{}", message);
+            return 1;
+        }
+    };
+
+    let mut total_problem_duration = Duration::new(0, 0);
+    let mut any_failed = false;
+    let mut reports = Vec::new();
+    let start = Instant::now();
+
+    // Text output is printed as we go so there's something to look at
+    // during the slow days, the other formats need everything up front.
+    let text = options.format == Format::Text;
+
+    let mut handle_report = |day: Day, report: Result<DayReport, AocError>, stats: Option<BenchStats>| {
+        let report = report.and_then(|mut report| {
+            if options.check {
+                check_answers(&day, &mut report, options.record)?;
+            }
+            Ok(report)
+        });
+
+        if text {
+            println!("\nDay {}: {}", day.day, day.name);
+        }
+
+        match &report {
+            Err(error) => {
+                if text {
+                    println!("  {}", error);
+                    print_diagnostic(error);
+                }
+
+                // A day that couldn't be run at all didn't get its answers
+                // right either.
+                any_failed |= options.check;
+            },
+            Ok(report) => {
+                if text {
+                    match &stats {
+                        None => print_run(report),
+                        Some(stats) => print_bench(report, stats)
+                    }
+                }
+
+                any_failed |= report.parts().any(|part| is_failure(&part.check));
+                total_problem_duration += report.duration();
+            }
+        }
+
+        reports.push((day, report));
+    };
+
+    match options.bench {
+        None => run_days(days, &options.input, options.jobs, options.timeout, |day, report| handle_report(day, report, None)),
+        Some(iterations) => {
+            for (day, parts) in days {
+                match bench(&day, iterations, &options.input, parts) {
+                    Err(error) => handle_report(day, Err(error), None),
+                    Ok(bench) => {
+                        let stats = (bench.parse, bench.part_one, bench.part_two, bench.total);
+                        handle_report(day, Ok(bench.report), Some(stats));
+                    }
+                }
+            }
+        }
+    }
+
+    // Not having somewhere to keep the history shouldn't sink the run, and
+    // stderr keeps this out of the json and csv.
+    if options.history {
+        let mode = match (options.bench, options.jobs) {
+            (Some(_), _) => Mode::Bench,
+            (None, 1) => Mode::Run,
+            (None, _) => Mode::Jobs
+        };
+        if let Err(error) = record_history(options.year, &options.input, mode, &reports) {
+            eprintln!("Couldn't record timing history: {}", error);
+        }
+    }
+
+    let totals = Totals {
+        total_duration: start.elapsed(),
+        problem_duration: total_problem_duration
+    };
+
+    match options.format {
+        // With --bench the problem time is the sum of the per-part medians.
+        Format::Text => match options.bench {
+            None => println!(
+                "\nTime - total: {}, problem: {}",
+                format_duration(totals.total_duration),
+                format_duration(totals.problem_duration)
+            ),
+            Some(iterations) => println!(
+                "\nTime - total: {}, problem (median of {}): {}",
+                format_duration(totals.total_duration),
+                iterations,
+                format_duration(totals.problem_duration)
+            )
+        },
+        Format::Json => print!("{}", to_json(&reports, &totals)),
+        Format::Csv => print!("{}", to_csv(&reports, &totals))
+    }
+
+    if any_failed { 1 } else { 0 }
+}
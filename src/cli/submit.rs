@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use aoc20::{get_day, run, Answer, AocError, InputSource};
+use aoc20::answers::Answers;
+use aoc20::client::{base_url, session_token, Client, Outcome};
+use aoc20::selection::{parse_day, Parts};
+use aoc20::submissions::submit;
+use aoc20::years::{Part, DEFAULT_YEAR};
+
+use crate::cli::{error_message, parse_input, parse_input_dir, parse_year, ANSWERS_DIR, SUBMISSIONS_DIR, USAGE};
+
+struct Options {
+    year: u32,
+    day: Option<u32>,
+    part: Option<Part>,
+    input: InputSource,
+    base_url: String
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        day: None,
+        part: None,
+        input: InputSource::default(),
+        base_url: base_url()
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--input" => options.input = parse_input(args.next())?,
+            "--input-dir" => options.input = InputSource::Dir(parse_input_dir(args.next())?),
+            "--base-url" => {
+                let url = args
+                    .next()
+                    .ok_or_else(|| "--base-url needs a URL".to_string())?;
+                options.base_url = url.to_string();
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.day.is_none() => options.day = Some(parse_day(arg).map_err(|_| format!("Bad day {}", arg))?),
+            _ if options.part.is_none() => {
+                options.part = match arg.as_str() {
+                    "1" => Some(Part::One),
+                    "2" => Some(Part::Two),
+                    _ => return Err(format!("Bad part {}", arg))
+                };
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    if options.day.is_none() || options.part.is_none() {
+        return Err("submit needs a day and a part".to_string());
+    }
+
+    Ok(options)
+}
+
+fn run_and_submit(options: &Options, day: u32, part: Part) -> Result<Outcome, AocError> {
+    let no_day = || AocError::Misc(format!("No implementation for {} day {}.", options.year, day));
+    let day = get_day(options.year, &day.to_string()).ok_or_else(no_day)?;
+
+    let (parts, number) = match part {
+        Part::One => (Parts::One, 1),
+        Part::Two => (Parts::Two, 2)
+    };
+    let report = run(&day, &options.input, parts)?;
+    let answer = match report.parts().next().map(|part| &part.result) {
+        // The site wants the letters the art spells out, which is down to
+        // whoever's reading it.
+        Some(Ok(Answer::NotImplemented)) => return Err(AocError::Misc("Part isn't implemented".to_string())),
+        Some(Ok(Answer::Art(_))) => return Err(AocError::Misc("Art answers have to be read and submitted by hand".to_string())),
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(error)) => return Err(AocError::Misc(error.to_string())),
+        None => return Err(AocError::Misc("Part didn't run".to_string()))
+    };
+
+    println!("Submitting {} for {} day {} part {}", answer, day.year, day.day, number);
+
+    let client = Client::new(&options.base_url, &session_token()?);
+    let outcome = submit(&client, Path::new(SUBMISSIONS_DIR), day.year, day.day.parse()?, part, &answer)?;
+
+    // Once the site agrees there's no reason to wait for --record.
+    if outcome == Outcome::Correct {
+        let dir = Path::new(ANSWERS_DIR);
+        let mut answers = Answers::load(dir, &day)?;
+        if answers.record(&report.part_one, &report.part_two) {
+            answers.save(dir, &day)?;
+        }
+    }
+
+    Ok(outcome)
+}
+
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    match run_and_submit(&options, options.day.unwrap(), options.part.unwrap()) {
+        Ok(outcome) => {
+            println!("{}", outcome);
+            if outcome == Outcome::Correct { 0 } else { 1 }
+        },
+        Err(error) => {
+            println!("{}", error_message(&error));
+            1
+        }
+    }
+}
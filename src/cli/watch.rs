@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc20::{AocError, InputSource};
+use aoc20::runner::{input_path, run_days};
+use aoc20::selection::Selection;
+use aoc20::years::DEFAULT_YEAR;
+
+use crate::cli::{parse_input, parse_input_dir, parse_year, print_diagnostic, select_days, USAGE};
+use crate::cli::run::print_run;
+
+struct Options {
+    year: u32,
+    selection: Option<Selection>,
+    input: InputSource,
+    interval: Duration
+}
+
+fn parse_seconds(option: &str, seconds: Option<&String>) -> Result<Duration, String> {
+    let seconds = seconds.ok_or_else(|| format!("{} needs a number of seconds", option))?;
+    let seconds: f64 = seconds
+        .parse()
+        .map_err(|_| format!("Bad number of seconds {}", seconds))?;
+    if !seconds.is_finite() || seconds <= 0. {
+        return Err(format!("{} needs a positive number of seconds", option));
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        year: DEFAULT_YEAR,
+        selection: None,
+        input: InputSource::default(),
+        interval: Duration::from_millis(500)
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_year(args.next())?,
+            "--input" => {
+                options.input = parse_input(args.next())?;
+                if options.input == InputSource::Stdin {
+                    return Err("There's nothing to watch on stdin".to_string());
+                }
+            },
+            "--input-dir" => options.input = InputSource::Dir(parse_input_dir(args.next())?),
+            "--interval" => options.interval = parse_seconds("--interval", args.next())?,
+            // A timed out day can't be stopped, only abandoned on its own
+            // thread, and watch would pile those up for as long as it runs.
+            "--timeout" => return Err("watch can't use --timeout, Ctrl-C stops a day that hangs".to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if options.selection.is_none() => {
+                options.selection = Some(arg.parse().map_err(|error: AocError| error.to_string())?);
+            },
+            _ => return Err(format!("Unexpected argument {}", arg))
+        }
+    }
+
+    match &options.selection {
+        Some(selection) if selection.0.len() == 1 => Ok(options),
+        _ => Err("watch needs exactly one day".to_string())
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+// Polls the input's modification time and re-runs the day whenever it
+// changes, until killed. A missing input is reported like any other run and
+// picked up once it appears. Only the input is watched: the solutions and
+// their embedded examples are compiled in, so changes to those need a rebuild
+// and a fresh watch anyway. An example saved to a file can be watched with
+// --input.
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{}\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let days = match select_days(options.year, &options.selection) {
+        Ok(days) => days,
+        Err(message) => {
+            println!("{}", message);
+            return 1;
+        }
+    };
+
+    // parse_args only lets through file inputs, which always have a path.
+    let path = match input_path(&days[0].0, &options.input) {
+        Some(path) => path,
+        None => return 2
+    };
+
+    let mut last_modified = None;
+    let mut first = true;
+    loop {
+        let now_modified = modified(&path);
+        if first || now_modified != last_modified {
+            first = false;
+            last_modified = now_modified;
+
+            // Clear the screen and move the cursor back to the top.
+            print!("\x1b[2J\x1b[H");
+            println!("Watching {}, Ctrl-C to stop", path.display());
+
+            run_days(days.clone(), &options.input, 1, None, |day, report| {
+                println!("\nDay {}: {}", day.day, day.name);
+                match report {
+                    Err(error) => {
+                        println!("  {}", error);
+                        print_diagnostic(&error);
+                    },
+                    Ok(report) => print_run(&report)
+                }
+            });
+        }
+
+        thread::sleep(options.interval);
+    }
+}
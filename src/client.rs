@@ -0,0 +1,415 @@
+use std::env;
+use std::fmt::{Display, Formatter, self};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::aoc_error::AocError;
+use crate::runner::year_path;
+use crate::years::Part;
+
+// Everything the site serves hangs off one base URL so it can be pointed at a
+// local server for testing, either with AOC_BASE_URL or --base-url.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+// The session cookie from a logged in browser. If the variable isn't set it's
+// read from ~/.config/aoc20/session instead.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+// The site asks automated tools to identify themselves.
+const USER_AGENT: &str = "aoc20 (https://github.com/skermes/aoc20)";
+
+pub fn base_url() -> String {
+    env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+pub fn session_path() -> Option<PathBuf> {
+    let config = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config")
+    };
+    Some(config.join("aoc20").join("session"))
+}
+
+pub fn session_token() -> Result<String, AocError> {
+    if let Ok(token) = env::var(SESSION_VAR) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let path = session_path()
+        .ok_or_else(|| AocError::Misc(format!("No session token, set {}", SESSION_VAR)))?;
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(AocError::Misc(format!(
+            "No session token, set {} or put it in {}",
+            SESSION_VAR,
+            path.display()
+        )))
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build()
+        }
+    }
+
+    fn url(&self, year: u32, day: u32, path: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, year, day, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, year: u32, day: u32) -> Result<String, AocError> {
+        let response = self.agent
+            .get(&self.url(year, day, "/input"))
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(http_error)?;
+
+        response.into_string().map_err(AocError::from)
+    }
+
+    // Posts an answer and works out what the site thought of it.
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: &str) -> Result<Outcome, AocError> {
+        let level = match part {
+            Part::One => "1",
+            Part::Two => "2"
+        };
+
+        let response = self.agent
+            .post(&self.url(year, day, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", level), ("answer", answer)])
+            .map_err(http_error)?;
+
+        Ok(parse_response(&response.into_string()?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, without a hint which way.
+    Wrong,
+    // Answers came in too quickly, the site says how long until the next one
+    // will be looked at.
+    RateLimited(Option<Duration>),
+    // The part had already been solved, so the answer wasn't checked.
+    AlreadySolved,
+    // Anything else, with the text of the response.
+    Unknown(String)
+}
+
+impl Outcome {
+    // A one word name for the submission log.
+    pub fn token(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited(_) => "rate-limited",
+            Outcome::AlreadySolved => "already-solved",
+            Outcome::Unknown(_) => "unknown"
+        }
+    }
+
+    pub fn from_token(token: &str) -> Option<Outcome> {
+        match token {
+            "correct" => Some(Outcome::Correct),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            "wrong" => Some(Outcome::Wrong),
+            "rate-limited" => Some(Outcome::RateLimited(None)),
+            "already-solved" => Some(Outcome::AlreadySolved),
+            "unknown" => Some(Outcome::Unknown(String::new())),
+            _ => None
+        }
+    }
+
+    // Whether the site actually judged the answer. Sending the same answer
+    // again after one of these can only give the same verdict.
+    pub fn is_verdict(&self) -> bool {
+        matches!(self, Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "Correct"),
+            Outcome::TooHigh => write!(f, "Wrong, too high"),
+            Outcome::TooLow => write!(f, "Wrong, too low"),
+            Outcome::Wrong => write!(f, "Wrong"),
+            Outcome::RateLimited(Some(wait)) => write!(f, "Rate limited, try again in {}s", wait.as_secs()),
+            Outcome::RateLimited(None) => write!(f, "Rate limited"),
+            Outcome::AlreadySolved => write!(f, "Already solved"),
+            Outcome::Unknown(text) if text.is_empty() => write!(f, "Unrecognized response"),
+            Outcome::Unknown(text) => write!(f, "Unrecognized response: {}", text)
+        }
+    }
+}
+
+// The response is a whole page, the part we care about is the <article>.
+fn response_text(page: &str) -> String {
+    let article = match (page.find("<article>"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Parses waits like "You have 4m 36s left to wait" or "You have 36s left".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left")?;
+
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+pub fn parse_response(page: &str) -> Outcome {
+    let text = response_text(page);
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+fn http_error(error: ureq::Error) -> AocError {
+    match error {
+        // The site explains itself in the body, e.g. for days that haven't
+        // unlocked yet, and that's more useful than the status alone.
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            match body.lines().next() {
+                Some(line) if !line.trim().is_empty() => AocError::Http(format!("{}: {}", status, line.trim())),
+                _ => AocError::Http(format!("{}", status))
+            }
+        },
+        ureq::Error::Transport(error) => AocError::Http(error.to_string())
+    }
+}
+
+// Downloads a day's input to <dir>/<year>/<day>.txt (see year_path) and
+// returns where it went. Inputs never change, so one that's already there is
+// left alone rather than asking the site for it again.
+pub fn fetch_input(client: &Client, dir: &Path, year: u32, day: u32) -> Result<PathBuf, AocError> {
+    let path = year_path(dir, year, &format!("{}.txt", day));
+    if path.exists() {
+        return Err(AocError::Misc(format!("{} already exists, not fetching it again", path.display())));
+    }
+
+    let input = client.input(year, day)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, input)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::process;
+    use std::thread::{self, JoinHandle};
+
+    // A stand-in for the site that answers each connection with the next
+    // canned (status, body) response, and hands back the requests it saw.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                ).unwrap();
+            }
+
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc20-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_and_caches_input() {
+        let (base_url, server) = serve(vec![(200, "1721\n979\n366\n")]);
+        let client = Client::new(&base_url, "abc123");
+        let dir = temp_dir("fetch");
+
+        let path = fetch_input(&client, &dir, 2020, 1).unwrap();
+        assert_eq!(path, dir.join("2020").join("1.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n366\n");
+
+        match fetch_input(&client, &dir, 2020, 1) {
+            Err(AocError::Misc(message)) => assert!(message.contains("already exists")),
+            other => panic!("Expected a cached input error, got {:?}", other)
+        }
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2020/day/1/input "));
+        assert!(requests[0].contains("session=abc123"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reports_site_errors() {
+        let (base_url, server) = serve(vec![(404, "Please don't repeatedly request this endpoint before it unlocks!")]);
+        let client = Client::new(&base_url, "abc123");
+        let dir = temp_dir("fetch-locked");
+
+        match fetch_input(&client, &dir, 2020, 25) {
+            Err(AocError::Http(message)) => assert!(message.starts_with("404: Please don't")),
+            other => panic!("Expected an HTTP error, got {:?}", other)
+        }
+        assert!(!dir.join("2020").join("25.txt").exists());
+
+        server.join().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parses_responses() {
+        let page = |text: &str| format!("<html><main><article><p>{}</p></article></main></html>", text);
+
+        assert_eq!(
+            parse_response(&page("That's the right answer! You are <em>one gold star</em> closer.")),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too high. Please wait one minute.")),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too low.")),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer. If you're stuck, make sure...")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 36s left to wait.")),
+            Outcome::RateLimited(Some(Duration::from_secs(276)))
+        );
+        assert_eq!(
+            parse_response(&page("You gave an answer too recently. You have 36s left to wait.")),
+            Outcome::RateLimited(Some(Duration::from_secs(36)))
+        );
+        assert_eq!(
+            parse_response(&page("You don't seem to be solving the right level.  Did you already complete it?")),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(parse_response(&page("Something else")), Outcome::Unknown("Something else".to_string()));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(vec![(200, "<article><p>That's the right answer!</p></article>")]);
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(client.submit(2020, 1, Part::Two, "241861950").unwrap(), Outcome::Correct);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2020/day/1/answer "));
+        assert!(requests[0].contains("session=abc123"));
+        assert!(requests[0].ends_with("level=2&answer=241861950"));
+    }
+}
@@ -1,11 +0,0 @@
-use crate::aoc_error::AocError;
-
-pub const NAME: &str = "Unknown";
-
-pub fn part_one(_input: &str) -> Result<String, AocError> {
-    Ok("Not implemented".to_string())
-}
-
-pub fn part_two(_input: &str) -> Result<String, AocError> {
-    Ok("Not implemented".to_string())
-}
\ No newline at end of file
@@ -0,0 +1,294 @@
+// Checks each day's parts against the reference versions on generated inputs.
+// The references do everything the slow, obvious way, so when the two
+// disagree it's most likely a shortcut in the real part that doesn't hold up.
+
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::runner::call_part;
+use crate::years::{Day, Generator, Part};
+
+// Which of the day's generators to check the part on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Inputs {
+    Usual,
+    Tricky
+}
+
+impl Inputs {
+    fn generator(&self, day: &Day) -> Generator {
+        match self {
+            Inputs::Usual => day.generate,
+            Inputs::Tricky => day.generate_tricky
+        }
+    }
+}
+
+pub struct Disagreement {
+    pub part: Part,
+    pub inputs: Inputs,
+    pub seed: u64,
+    pub size: usize,
+    // The generated input, cut down to as few lines as still disagree.
+    pub input: String,
+    pub answer: Result<Answer, AocError>,
+    pub reference: Result<Answer, AocError>
+}
+
+pub enum Outcome {
+    // How many inputs both answered the same, none if the day has no
+    // generator or the part no reference.
+    Agreed(usize),
+    Disagreed(Disagreement)
+}
+
+// Which way round a part and its reference differ. Inputs are only shrunk
+// as long as they keep differing the same way, so a wrong answer doesn't
+// turn into an input that's simply malformed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mismatch {
+    Answers,
+    PartFailed,
+    ReferenceFailed
+}
+
+fn mismatch(answer: &Result<Answer, AocError>, reference: &Result<Answer, AocError>) -> Option<Mismatch> {
+    match (answer, reference) {
+        (Ok(Answer::NotImplemented), _) | (_, Ok(Answer::NotImplemented)) => None,
+        (Ok(answer), Ok(reference)) if answer == reference => None,
+        (Ok(_), Ok(_)) => Some(Mismatch::Answers),
+        (Err(_), Ok(_)) => Some(Mismatch::PartFailed),
+        (Ok(_), Err(_)) => Some(Mismatch::ReferenceFailed),
+        // Both turning the input down is as good as agreeing.
+        (Err(_), Err(_)) => None
+    }
+}
+
+fn answers(day: &Day, part: Part, input: &str) -> (Result<Answer, AocError>, Result<Answer, AocError>) {
+    (call_part(day.solution(part), input), call_part(day.reference(part), input))
+}
+
+fn valid(day: &Day, input: &str) -> bool {
+    (day.validate)(input).is_none_or(|problems| problems.is_empty())
+}
+
+// Drops lines from the input for as long as it still disagrees the same way,
+// taking out big chunks first and working down to single lines. If the
+// validator had nothing to say about the input it has to stay that way, so
+// that a wrong answer doesn't shrink into one the part never promised.
+// Tricky inputs start out breaking the rules, and can go on doing so.
+fn shrink(day: &Day, part: Part, input: &str, kind: Mismatch) -> String {
+    let was_valid = valid(day, input);
+    let still_disagrees = |input: &str| {
+        let (answer, reference) = answers(day, part, input);
+        !input.is_empty() && (valid(day, input) || !was_valid) && mismatch(&answer, &reference) == Some(kind)
+    };
+
+    let mut lines: Vec<&str> = input.split('\n').collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < lines.len() {
+            let mut fewer = lines.clone();
+            fewer.drain(start..(start + chunk).min(lines.len()));
+            if still_disagrees(&fewer.join("\n")) {
+                lines = fewer;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    lines.join("\n")
+}
+
+// Tries every seed below seeds at sizes doubling from 1 up to max_size, so
+// the first disagreement found comes from as small an input as the generator
+// makes, and then shrinks that.
+pub fn check_part(day: &Day, part: Part, inputs: Inputs, seeds: u64, max_size: usize) -> Outcome {
+    let mut sizes = Vec::new();
+    let mut size = 1;
+    while size < max_size {
+        sizes.push(size);
+        size *= 2;
+    }
+    sizes.push(max_size);
+
+    let mut agreed = 0;
+    for size in sizes {
+        for seed in 0..seeds {
+            let input = match (inputs.generator(day))(&mut Rng::new(seed), size) {
+                Some(input) => input,
+                None => return Outcome::Agreed(0)
+            };
+
+            let (answer, reference) = answers(day, part, &input);
+            if let Some(kind) = mismatch(&answer, &reference) {
+                let input = shrink(day, part, &input, kind);
+                let (answer, reference) = answers(day, part, &input);
+                return Outcome::Disagreed(Disagreement { part, inputs, seed, size, input, answer, reference });
+            }
+            if answer.map_or(true, |answer| answer.is_implemented()) &&
+               reference.map_or(true, |answer| answer.is_implemented()) {
+                agreed += 1;
+            }
+        }
+    }
+
+    Outcome::Agreed(agreed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::{get_day, years, Solution};
+
+    // Parts that take too long in a debug build to run over and over, day
+    // 15's reference plays thirty million turns with a HashMap and day 17's
+    // part works through every cell around the cubes. aoc20 crosscheck
+    // covers them in a release build.
+    const SLOW: &[(&str, Part)] = &[("15", Part::Two), ("17", Part::Two)];
+
+    #[test]
+    fn references_pass_the_examples() {
+        let mut failures = Vec::new();
+
+        for year in years() {
+            for day in (1..=25).filter_map(|day| get_day(year, &day.to_string())) {
+                // Examples with their own solution use different constants
+                // from the real puzzle, which the references don't know about.
+                let examples = day.examples
+                    .iter()
+                    .filter(|example| example.solution.is_none())
+                    .filter(|example| !SLOW.contains(&(day.day.as_str(), example.part)));
+
+                for example in examples {
+                    match call_part(day.reference(example.part), example.input.trim()) {
+                        Ok(Answer::NotImplemented) => {},
+                        Ok(answer) if answer.to_line() == example.expected => {},
+                        result => failures.push(format!(
+                            "{} day {} {:?}: expected {}, got {:?}",
+                            year, day.day, example.part, example.expected, result
+                        ))
+                    }
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "Failed examples:\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn parts_agree_with_references() {
+        let mut failures = Vec::new();
+
+        for year in years() {
+            for day in (1..=25).filter_map(|day| get_day(year, &day.to_string())) {
+                for part in IntoIterator::into_iter([Part::One, Part::Two]) {
+                    if SLOW.contains(&(day.day.as_str(), part)) {
+                        continue;
+                    }
+
+                    if let Outcome::Disagreed(disagreement) = check_part(&day, part, Inputs::Usual, 4, 16) {
+                        failures.push(format!(
+                            "{} day {} {:?} seed {} size {}: {:?} but the reference says {:?}\n{}",
+                            year, day.day, part, disagreement.seed, disagreement.size,
+                            disagreement.answer, disagreement.reference, disagreement.input
+                        ));
+                    }
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "Disagreements:\n{}", failures.join("\n\n"));
+    }
+
+    // Each of these days has a tricky generator aimed at the shortcut its part
+    // two takes, which crosscheck should catch out.
+    #[test]
+    fn tricky_inputs_break_the_shortcuts() {
+        let shortcuts = [("10", "Found a 2"), ("13", "Non-prime divisor"), ("16", "Can't tell which field")];
+        for (day, error) in IntoIterator::into_iter(shortcuts) {
+            let day = get_day(2020, day).unwrap();
+            match check_part(&day, Part::Two, Inputs::Tricky, 4, 16) {
+                Outcome::Disagreed(disagreement) => {
+                    let answer = disagreement.answer.unwrap_err().to_string();
+                    assert!(answer.contains(error), "Day {}: {}", day.day, answer);
+                    assert!(disagreement.reference.is_ok(), "Day {}: {}", day.day, disagreement.input);
+                },
+                Outcome::Agreed(_) => panic!("Day {} agreed on every tricky input", day.day)
+            }
+        }
+    }
+
+    // The validator objects to the two jolt gaps in day 10's tricky inputs,
+    // which used to stop them shrinking at all.
+    #[test]
+    fn tricky_inputs_shrink() {
+        let day = get_day(2020, "10").unwrap();
+        let input = (day.generate_tricky)(&mut Rng::new(0), 64).unwrap();
+        assert!(!valid(&day, &input));
+
+        let shrunk = shrink(&day, Part::Two, &input, Mismatch::PartFailed);
+        assert!(shrunk.lines().count() < 5, "{}", shrunk);
+    }
+
+    // Counts lines, but part one loses count of any starting with a q.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Parsed<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+            Ok(input.lines().collect())
+        }
+
+        fn part_one(lines: &Vec<&str>) -> Result<Answer, AocError> {
+            Ok(lines.iter().filter(|line| !line.starts_with('q')).count().into())
+        }
+
+        fn part_two(lines: &Vec<&str>) -> Result<Answer, AocError> {
+            Ok(lines.len().into())
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+            let lines: Vec<String> = (0..size)
+                .map(|_| (0..3).map(|_| (b'a' + rng.between(0, 25) as u8) as char).collect())
+                .collect();
+            Some(lines.join("\n"))
+        }
+
+        fn reference_one(input: &str) -> Result<Answer, AocError> {
+            Ok(input.lines().count().into())
+        }
+
+        fn reference_two(input: &str) -> Result<Answer, AocError> {
+            Ok(input.lines().count().into())
+        }
+    }
+
+    fn lines_day() -> Day {
+        Day::from_solution::<Lines>(2020, "0", "Lines", &[])
+    }
+
+    #[test]
+    fn disagreements_are_shrunk() {
+        let day = lines_day();
+
+        let disagreement = match check_part(&day, Part::One, Inputs::Usual, 10, 64) {
+            Outcome::Disagreed(disagreement) => disagreement,
+            Outcome::Agreed(_) => panic!("Didn't find the q lines")
+        };
+        assert!(disagreement.input.starts_with('q'), "{}", disagreement.input);
+        assert_eq!(disagreement.input.lines().count(), 1);
+        assert_eq!(disagreement.answer.unwrap(), Answer::Int(0));
+        assert_eq!(disagreement.reference.unwrap(), Answer::Int(1));
+
+        match check_part(&day, Part::Two, Inputs::Usual, 3, 8) {
+            // Sizes 1, 2, 4 and 8 for each seed.
+            Outcome::Agreed(inputs) => assert_eq!(inputs, 12),
+            Outcome::Disagreed(disagreement) => panic!("{}", disagreement.input)
+        }
+    }
+}
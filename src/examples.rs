@@ -0,0 +1,78 @@
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::runner::call_part;
+use crate::years::{Day, Example};
+
+pub struct ExampleResult {
+    pub example: Example,
+    pub result: Result<Answer, AocError>
+}
+
+impl ExampleResult {
+    pub fn passed(&self) -> bool {
+        matches!(&self.result, Ok(answer) if answer.to_line() == self.example.expected)
+    }
+}
+
+pub fn run_example(day: &Day, example: &Example) -> ExampleResult {
+    let solution = example.solution.unwrap_or_else(|| day.solution(example.part));
+
+    // Examples are written with a leading newline to keep them readable, the
+    // real inputs get trimmed by the runner too.
+    ExampleResult {
+        example: *example,
+        result: call_part(solution, example.input.trim()).map_err(|error| day.locate(error))
+    }
+}
+
+pub fn run_examples(day: &Day) -> Vec<ExampleResult> {
+    day.examples
+        .iter()
+        .map(|example| run_example(day, example))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::{get_day, years};
+
+    #[test]
+    fn all_examples_pass() {
+        let mut failures = Vec::new();
+
+        for year in years() {
+            for day in (1..=25).filter_map(|day| get_day(year, &day.to_string())) {
+                for result in run_examples(&day) {
+                    if !result.passed() {
+                        failures.push(format!(
+                            "{} day {} {:?}: expected {}, got {:?}",
+                            year, day.day, result.example.part, result.example.expected, result.result
+                        ));
+                    }
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "Failed examples:\n{}", failures.join("\n"));
+    }
+
+    // The examples are inputs the solutions handle, so the validators had
+    // better not object to them.
+    #[test]
+    fn examples_are_valid_inputs() {
+        let mut failures = Vec::new();
+
+        for year in years() {
+            for day in (1..=25).filter_map(|day| get_day(year, &day.to_string())) {
+                for example in day.examples {
+                    for problem in (day.validate)(example.input.trim()).unwrap_or_default() {
+                        failures.push(format!("{} day {} {:?}: {}", year, day.day, example.part, problem));
+                    }
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "Invalid examples:\n{}", failures.join("\n"));
+    }
+}
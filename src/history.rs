@@ -0,0 +1,322 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::aoc_error::AocError;
+use crate::runner::DayReport;
+use crate::years::Part;
+
+// Every run's timings are appended to history/<year>.txt, one line per parse
+// or part with when it ran, the commit it ran at, the day, which step it was,
+// how long it took in nanoseconds and how it was run:
+//
+//     1607058000 3f2a1c9 1 parse 55000 run
+//     1607058000 3f2a1c9 1 1 20000 run
+//     1607058000 3f2a1c9+ 1 2 16000 bench
+//
+// A + after the commit means the working tree had uncommitted changes. Lines
+// from before the mode was recorded don't have one and count as plain runs.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(Part)
+}
+
+impl Step {
+    fn token(&self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part(Part::One) => "1",
+            Step::Part(Part::Two) => "2"
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Step> {
+        match token {
+            "parse" => Some(Step::Parse),
+            "1" => Some(Step::Part(Part::One)),
+            "2" => Some(Step::Part(Part::Two)),
+            _ => None
+        }
+    }
+}
+
+// How the timing was taken. A median from --bench, a single run, and a single
+// run sharing the machine with --jobs workers aren't comparable with each
+// other, so only timings taken the same way are compared.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Run,
+    Bench,
+    Jobs
+}
+
+impl Mode {
+    fn token(&self) -> &'static str {
+        match self {
+            Mode::Run => "run",
+            Mode::Bench => "bench",
+            Mode::Jobs => "jobs"
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Mode> {
+        match token {
+            "run" => Some(Mode::Run),
+            "bench" => Some(Mode::Bench),
+            "jobs" => Some(Mode::Jobs),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub time: u64,
+    pub commit: String,
+    pub day: u32,
+    pub step: Step,
+    pub duration: Duration,
+    pub mode: Mode
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct History {
+    pub timings: Vec<Timing>
+}
+
+fn history_path(dir: &Path, year: u32) -> PathBuf {
+    dir.join(format!("{}.txt", year))
+}
+
+impl Timing {
+    fn parse(line: &str) -> Result<Timing, AocError> {
+        let bad_line = || AocError::Misc(format!("Bad history line {}", line));
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let mode = match fields.len() {
+            5 => Mode::Run,
+            6 => Mode::from_token(fields[5]).ok_or_else(bad_line)?,
+            _ => return Err(bad_line())
+        };
+
+        Ok(Timing {
+            time: fields[0].parse()?,
+            commit: fields[1].to_string(),
+            day: fields[2].parse()?,
+            step: Step::from_token(fields[3]).ok_or_else(bad_line)?,
+            duration: Duration::from_nanos(fields[4].parse()?),
+            mode
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}\n",
+            self.time, self.commit, self.day, self.step.token(), self.duration.as_nanos(), self.mode.token()
+        )
+    }
+}
+
+// The commit the working tree is at, with a + if it's been changed since.
+// Timings from outside a git checkout are put down to "unknown".
+pub fn current_commit() -> String {
+    let head = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    let head = match head {
+        Some(head) if !head.is_empty() => head,
+        _ => return "unknown".to_string()
+    };
+
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .map(|output| !output.stdout.is_empty())
+        .unwrap_or(false);
+
+    if dirty { head + "+" } else { head }
+}
+
+// The timings worth keeping from a report. Parts that didn't come up with an
+// answer haven't got a meaningful time.
+pub fn timings(day: u32, report: &DayReport, time: u64, commit: &str, mode: Mode) -> Vec<Timing> {
+    let timing = |step, duration| Timing { time, commit: commit.to_string(), day, step, duration, mode };
+
+    let parts = [(Part::One, &report.part_one), (Part::Two, &report.part_two)];
+    let mut timings = vec![timing(Step::Parse, report.parse)];
+    for (part, part_report) in IntoIterator::into_iter(parts) {
+        if let Some(part_report) = part_report.as_ref().filter(|part_report| part_report.result.is_ok()) {
+            timings.push(timing(Step::Part(part), part_report.duration));
+        }
+    }
+    timings
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0)
+}
+
+impl History {
+    pub fn parse(s: &str) -> Result<History, AocError> {
+        let timings = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Timing::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { timings })
+    }
+
+    pub fn load(dir: &Path, year: u32) -> Result<History, AocError> {
+        match fs::read_to_string(history_path(dir, year)) {
+            Ok(s) => History::parse(&s),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(error) => Err(error.into())
+        }
+    }
+
+    pub fn append(dir: &Path, year: u32, timings: &[Timing]) -> Result<(), AocError> {
+        fs::create_dir_all(dir)?;
+
+        let lines: String = timings.iter().map(Timing::to_line).collect();
+        let mut file = OpenOptions::new().create(true).append(true).open(history_path(dir, year))?;
+        file.write_all(lines.as_bytes())?;
+
+        Ok(())
+    }
+
+    // The latest timing for each day and step, against the one it's being
+    // compared to: the timing before it taken the same way by default, or the
+    // latest taken the same way from a commit starting with baseline. Steps
+    // with nothing to compare against are left out.
+    pub fn compare(&self, baseline: Option<&str>, days: &[u32]) -> Vec<Comparison> {
+        let mut comparisons = Vec::new();
+
+        for &day in days {
+            for &step in &[Step::Parse, Step::Part(Part::One), Step::Part(Part::Two)] {
+                let mut runs = self.timings
+                    .iter()
+                    .rev()
+                    .filter(|timing| timing.day == day && timing.step == step);
+
+                let latest = match runs.next() {
+                    Some(latest) => latest,
+                    None => continue
+                };
+                let mut runs = runs.filter(|timing| timing.mode == latest.mode);
+                let earlier = match baseline {
+                    None => runs.next(),
+                    Some(commit) => runs.find(|timing| timing.commit.starts_with(commit))
+                };
+
+                if let Some(earlier) = earlier {
+                    comparisons.push(Comparison {
+                        day,
+                        step,
+                        baseline: earlier.clone(),
+                        latest: latest.clone()
+                    });
+                }
+            }
+        }
+
+        comparisons
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u32,
+    pub step: Step,
+    pub baseline: Timing,
+    pub latest: Timing
+}
+
+impl Comparison {
+    // How much slower the latest timing is, in percent. Negative is faster.
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.duration.as_secs_f64();
+        let latest = self.latest.duration.as_secs_f64();
+        if baseline == 0. {
+            return 0.;
+        }
+        (latest - baseline) / baseline * 100.
+    }
+
+    pub fn slower_than(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HISTORY: &str = "
+100 aaaaaaa 1 parse 1000
+100 aaaaaaa 1 1 2000
+200 bbbbbbb 1 1 3000 run
+200 bbbbbbb 2 2 500
+300 ccccccc+ 1 1 1500 run";
+
+    #[test]
+    fn parse_history() {
+        let history = History::parse(HISTORY).unwrap();
+        assert_eq!(history.timings.len(), 5);
+        assert_eq!(history.timings[4].commit, "ccccccc+");
+        assert_eq!(history.timings[4].step, Step::Part(Part::One));
+        assert_eq!(history.timings[4].to_line(), "300 ccccccc+ 1 1 1500 run\n");
+        assert_eq!(history.timings[0].mode, Mode::Run);
+
+        assert!(History::parse("100 aaaaaaa 1 3 1000").is_err());
+        assert!(History::parse("100 aaaaaaa 1 1").is_err());
+        assert!(History::parse("100 aaaaaaa 1 1 1000 fast").is_err());
+    }
+
+    #[test]
+    fn compares_against_the_baseline() {
+        let history = History::parse(HISTORY).unwrap();
+
+        // By default each step is compared with its previous run, steps that
+        // only ran once have nothing to compare with.
+        let comparisons = history.compare(None, &[1, 2]);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline.commit, "bbbbbbb");
+        assert_eq!(comparisons[0].change(), -50.);
+
+        let comparisons = history.compare(Some("aaa"), &[1, 2]);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline.duration, Duration::from_nanos(2000));
+        assert!((comparisons[0].change() + 25.).abs() < 1e-9);
+        assert!(!comparisons[0].slower_than(10.));
+    }
+
+    #[test]
+    fn only_compares_timings_taken_the_same_way() {
+        let history = History::parse("
+100 aaaaaaa 1 1 2000 bench
+200 bbbbbbb 1 1 9000 jobs
+300 ccccccc 1 1 5000 run
+400 ddddddd 1 1 2200 bench
+500 eeeeeee 1 2 100 bench
+600 fffffff 1 2 400 run").unwrap();
+
+        let comparisons = history.compare(None, &[1]);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline.commit, "aaaaaaa");
+        assert_eq!(comparisons[0].latest.commit, "ddddddd");
+
+        assert!(history.compare(Some("ccc"), &[1]).is_empty());
+    }
+}
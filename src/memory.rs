@@ -0,0 +1,137 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// What a piece of code allocated while it ran.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Memory {
+    // Total bytes asked for, whether or not they were freed again.
+    pub allocated: u64,
+    pub allocations: u64,
+    // The most memory it had live at once, over what was already live when
+    // it started.
+    pub peak: u64
+}
+
+// Counts are kept per thread so days running side by side on the worker pool
+// don't see each other's allocations. These have to be const initialized,
+// anything lazier would allocate from inside the allocator.
+thread_local! {
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+// Thread locals can be gone while a thread is shutting down, allocations
+// then just go uncounted.
+fn record_allocation(size: usize) {
+    let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + size as u64));
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    record_live(size as i64);
+}
+
+fn record_live(change: i64) {
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + change);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+// A global allocator that counts what each thread allocates on top of the
+// system allocator. The bookkeeping isn't free, so it's only installed when
+// the count-allocations feature is on.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record_live(-(layout.size() as i64));
+        System.dealloc(ptr, layout)
+    }
+
+    // A realloc counts as an allocation of the new size. Live memory only
+    // changes by the difference, which undercounts the moment where both
+    // the old and new blocks exist, but that's up to the system allocator.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + new_size as u64));
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        record_live(new_size as i64 - layout.size() as i64);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+// Runs f and counts what it allocated on this thread. Without the counting
+// allocator installed there's nothing to report.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let allocated = ALLOCATED.with(Cell::get);
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let live = LIVE.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(live));
+
+    let result = f();
+
+    let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+    let memory = Memory {
+        allocated: ALLOCATED.with(Cell::get) - allocated,
+        allocations: ALLOCATIONS.with(Cell::get) - allocations,
+        peak: (peak - live).max(0) as u64
+    };
+
+    (result, Some(memory))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1 << 10 {
+        format!("{}B", bytes)
+    } else if bytes < 1 << 20 {
+        format!("{:.1}KiB", bytes as f64 / (1u64 << 10) as f64)
+    } else if bytes < 1 << 30 {
+        format!("{:.1}MiB", bytes as f64 / (1u64 << 20) as f64)
+    } else {
+        format!("{:.1}GiB", bytes as f64 / (1u64 << 30) as f64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn byte_sizes() {
+        assert_eq!(format_bytes(12), "12B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(120_000_000), "114.4MiB");
+    }
+
+    #[test]
+    #[cfg(feature = "count-allocations")]
+    fn counts_what_was_allocated() {
+        let (_, memory) = measure(|| {
+            let big = vec![0u8; 1 << 20];
+            drop(big);
+            vec![0u8; 1 << 10]
+        });
+        let memory = memory.unwrap();
+
+        assert!(memory.allocations >= 2);
+        assert!(memory.allocated >= (1 << 20) + (1 << 10));
+        assert!(memory.peak >= 1 << 20 && memory.peak < (1 << 20) + (1 << 12));
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::answers::Check;
+use crate::memory::Memory;
+use crate::years::Day;
+use crate::runner::{DayReport, PartReport};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv
+}
+
+impl FromStr for Format {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(AocError::Misc(format!("Unknown output format {}", s)))
+        }
+    }
+}
+
+pub struct Totals {
+    pub total_duration: Duration,
+    pub problem_duration: Duration
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => { let _ = write!(escaped, "\\u{:04x}", c as u32); },
+            c => escaped.push(c)
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_error(error: &AocError) -> String {
+    format!(
+        "\"status\": \"error\", \"error\": {}, \"message\": {}",
+        json_string(error.kind()),
+        json_string(&error.to_string())
+    )
+}
+
+fn json_check(check: &Option<Check>) -> String {
+    match check {
+        None => "".to_string(),
+        Some(Check::Fail(expected)) => format!(
+            ", \"check\": \"FAIL\", \"expected\": {}",
+            json_string(expected)
+        ),
+        Some(check) => format!(", \"check\": {}", json_string(check.status()))
+    }
+}
+
+// Prefix tells the day's parse numbers apart from its parts'.
+fn json_memory(prefix: &str, memory: &Option<Memory>) -> String {
+    match memory {
+        None => "".to_string(),
+        Some(memory) => format!(
+            ", \"{0}allocated_bytes\": {1}, \"{0}allocations\": {2}, \"{0}peak_bytes\": {3}",
+            prefix,
+            memory.allocated,
+            memory.allocations,
+            memory.peak
+        )
+    }
+}
+
+fn json_part(part: usize, report: &PartReport) -> String {
+    let status = match &report.result {
+        Ok(Answer::NotImplemented) => "\"status\": \"not_implemented\"".to_string(),
+        Ok(answer) => format!("\"status\": \"ok\", \"answer\": {}", json_string(&answer.to_string())),
+        Err(error) => json_error(error)
+    };
+
+    format!(
+        "{{\"part\": {}, {}, \"duration_ns\": {}{}{}}}",
+        part,
+        status,
+        report.duration.as_nanos(),
+        json_memory("", &report.memory),
+        json_check(&report.check)
+    )
+}
+
+pub fn to_json(reports: &[(Day, Result<DayReport, AocError>)], totals: &Totals) -> String {
+    let days: Vec<String> = reports
+        .iter()
+        .map(|(day, report)| {
+            let body = match report {
+                Err(error) => json_error(error),
+                Ok(report) => {
+                    let parts: Vec<String> = report.part_one
+                        .iter()
+                        .map(|part| json_part(1, part))
+                        .chain(report.part_two.iter().map(|part| json_part(2, part)))
+                        .map(|part| format!("      {}", part))
+                        .collect();
+
+                    format!(
+                        "\"status\": \"ok\", \"parse_ns\": {}{}, \"parts\": [\n{}\n    ]",
+                        report.parse.as_nanos(),
+                        json_memory("parse_", &report.parse_memory),
+                        parts.join(",\n")
+                    )
+                }
+            };
+
+            format!(
+                "    {{\"year\": {}, \"day\": {}, \"name\": {}, {}}}",
+                day.year,
+                day.day,
+                json_string(&day.name),
+                body
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\n  \"days\": [\n{}\n  ],\n  \"total_duration_ns\": {},\n  \"problem_duration_ns\": {}\n}}\n",
+        days.join(",\n"),
+        totals.total_duration.as_nanos(),
+        totals.problem_duration.as_nanos()
+    )
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace("\"", "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_row(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+    fields.join(",") + "\n"
+}
+
+// Allocated bytes, allocation count and peak bytes, empty when memory wasn't
+// measured.
+fn csv_memory(memory: &Option<Memory>) -> [String; 3] {
+    match memory {
+        None => Default::default(),
+        Some(memory) => [memory.allocated.to_string(), memory.allocations.to_string(), memory.peak.to_string()]
+    }
+}
+
+fn csv_part(day: &Day, part: &str, report: &PartReport) -> String {
+    let year = day.year.to_string();
+    let duration = report.duration.as_nanos().to_string();
+    let check = report.check.as_ref().map(|check| check.status()).unwrap_or("");
+    let [allocated, allocations, peak] = csv_memory(&report.memory);
+    match &report.result {
+        Ok(Answer::NotImplemented) => csv_row(&[
+            &year, &day.day, &day.name, part, "not_implemented", "", "", "", &duration, check, &allocated, &allocations, &peak
+        ]),
+        Ok(answer) => csv_row(&[
+            &year, &day.day, &day.name, part, "ok", &answer.to_string(), "", "", &duration, check, &allocated, &allocations, &peak
+        ]),
+        Err(error) => csv_row(&[
+            &year, &day.day, &day.name, part, "error", "", error.kind(), &error.to_string(), &duration, check, &allocated, &allocations, &peak
+        ])
+    }
+}
+
+// One row per part plus a "parse" row for parsing the input, with a day-level
+// row (empty part) for days that couldn't run at all and two trailing rows for
+// the run totals.
+pub fn to_csv(reports: &[(Day, Result<DayReport, AocError>)], totals: &Totals) -> String {
+    let mut csv = csv_row(&[
+        "year", "day", "name", "part", "status", "answer", "error", "message", "duration_ns", "check",
+        "allocated_bytes", "allocations", "peak_bytes"
+    ]);
+
+    for (day, report) in reports {
+        match report {
+            Err(error) => csv.push_str(&csv_row(&[
+                &day.year.to_string(), &day.day, &day.name, "", "error", "", error.kind(), &error.to_string(), "", "", "", "", ""
+            ])),
+            Ok(report) => {
+                let [allocated, allocations, peak] = csv_memory(&report.parse_memory);
+                csv.push_str(&csv_row(&[
+                    &day.year.to_string(), &day.day, &day.name, "parse", "ok", "", "", "", &report.parse.as_nanos().to_string(), "",
+                    &allocated, &allocations, &peak
+                ]));
+                if let Some(part) = &report.part_one {
+                    csv.push_str(&csv_part(day, "1", part));
+                }
+                if let Some(part) = &report.part_two {
+                    csv.push_str(&csv_part(day, "2", part));
+                }
+            }
+        }
+    }
+
+    let total = totals.total_duration.as_nanos().to_string();
+    let problem = totals.problem_duration.as_nanos().to_string();
+    csv.push_str(&csv_row(&["", "", "total", "", "", "", "", "", &total, "", "", "", ""]));
+    csv.push_str(&csv_row(&["", "", "problem", "", "", "", "", "", &problem, "", "", "", ""]));
+
+    csv
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_escapes() {
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn csv_quotes() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
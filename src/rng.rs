@@ -0,0 +1,110 @@
+// A small seeded random number generator for making up puzzle inputs. It's
+// SplitMix64, which is plenty random for that, and having it here rather than
+// pulling in a crate means a seed gives the same input on every version.
+
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number from low to high, inclusive.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "Empty range {}..={}", low, high);
+        match (high - low).checked_add(1) {
+            // Multiplying out to 128 bits and keeping the top half is less
+            // biased than taking a remainder.
+            Some(span) => low + ((self.next_u64() as u128 * span as u128) >> 64) as u64,
+            None => self.next_u64()
+        }
+    }
+
+    // A signed number from low to high, inclusive.
+    pub fn between_signed(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "Empty range {}..={}", low, high);
+        let span = high.wrapping_sub(low) as u64;
+        low.wrapping_add(self.between(0, span) as i64)
+    }
+
+    // An index into something with len items.
+    pub fn below(&mut self, len: usize) -> usize {
+        assert!(len > 0, "Nothing to choose from");
+        self.between(0, len as u64 - 1) as usize
+    }
+
+    // True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // The top 53 bits make an evenly spread f64 in [0, 1).
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        let from_a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let from_b: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+        let from_c: Vec<u64> = (0..10).map(|_| c.next_u64()).collect();
+        assert_eq!(from_a, from_b);
+        assert_ne!(from_a, from_c);
+
+        // SplitMix64's published first output for a seed of 0.
+        assert_eq!(Rng::new(0).next_u64(), 0xe220a8397b1dcdaf);
+    }
+
+    #[test]
+    fn ranges_are_inclusive() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let n = rng.between(3, 7);
+            assert!((3..=7).contains(&n));
+            seen[n as usize - 3] = true;
+
+            let n = rng.between_signed(-2, 2);
+            assert!((-2..=2).contains(&n));
+        }
+        assert!(seen.iter().all(|seen| *seen));
+
+        assert_eq!(rng.between(5, 5), 5);
+        rng.between(0, u64::MAX);
+        rng.between_signed(i64::MIN, i64::MAX);
+    }
+
+    #[test]
+    fn shuffles_keep_everything() {
+        let mut rng = Rng::new(7);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<u32>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,505 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::io::{self, Read};
+use std::fmt::Display;
+use std::fs::File;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, Once};
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Instant, Duration};
+
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::answers::Check;
+use crate::memory::{self, Memory};
+use crate::years::{Day, Part, Parsed, PartFn, DEFAULT_YEAR};
+use crate::selection::Parts;
+
+pub fn format_result<V, E>(result: &Result<V, E>) -> String
+    where V: Display,
+          E: Display
+{
+    match result {
+        Err(error) => format!("{}", error),
+        Ok(solution) => format!("{}", solution)
+    }
+}
+
+// The answer column of the text report. Numbers are right aligned so they line
+// up, unimplemented parts are flagged and art, which doesn't fit on one line,
+// is left for the caller to print underneath.
+pub fn format_answer(result: &Result<Answer, AocError>) -> String {
+    match result {
+        Ok(answer) if answer.is_number() => format!("{:>40}", answer),
+        Ok(Answer::NotImplemented) => format!("{:40}", "-- not implemented --"),
+        Ok(Answer::Art(rows)) => format!("{:40}", format!("({} rows of art)", rows.len())),
+        result => format!("{:40}", format_result(result))
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+
+    if micros < 1_000 {
+        format!("{}\u{03BC}s", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", (micros as f64) / 1_000.)
+    } else {
+        format!("{:.1}s", (micros as f64) / 1_000_000.)
+    }
+}
+
+pub struct PartReport {
+    pub result: Result<Answer, AocError>,
+    pub duration: Duration,
+    // Only filled in when checking against recorded answers.
+    pub check: Option<Check>,
+    // Only measured with the count-allocations feature.
+    pub memory: Option<Memory>
+}
+
+impl PartReport {
+    pub fn new(result: Result<Answer, AocError>, duration: Duration) -> PartReport {
+        PartReport { result, duration, check: None, memory: None }
+    }
+}
+
+// Parts that weren't selected to run are None. The input is parsed once for
+// both parts, that time is counted separately from theirs.
+pub struct DayReport {
+    pub parse: Duration,
+    pub parse_memory: Option<Memory>,
+    pub part_one: Option<PartReport>,
+    pub part_two: Option<PartReport>
+}
+
+impl DayReport {
+    pub fn duration(&self) -> Duration {
+        self.parse + self.parts().map(|part| part.duration).sum::<Duration>()
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = &PartReport> {
+        self.part_one.iter().chain(self.part_two.iter())
+    }
+}
+
+// Per-year files live in <dir>/<year>/<file>. Before there were years
+// everything was 2020 and sat directly in <dir>, so 2020 still falls back to
+// that when the year directory doesn't have the file.
+pub fn year_path(dir: &Path, year: u32, file: &str) -> PathBuf {
+    let path = dir.join(year.to_string()).join(file);
+    let legacy_path = dir.join(file);
+
+    if year == DEFAULT_YEAR && !path.exists() && legacy_path.exists() {
+        legacy_path
+    } else {
+        path
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    // Looks for <dir>/<year>/<day>.txt, see year_path.
+    Dir(PathBuf),
+    File(PathBuf),
+    Stdin
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Dir(PathBuf::from("inputs"))
+    }
+}
+
+// The file the day's input comes from, None for stdin.
+pub fn input_path(day: &Day, source: &InputSource) -> Option<PathBuf> {
+    match source {
+        InputSource::Dir(dir) => Some(year_path(dir, day.year, &format!("{}.txt", day.day))),
+        InputSource::File(path) => Some(path.clone()),
+        InputSource::Stdin => None
+    }
+}
+
+pub fn read_input(day: &Day, source: &InputSource) -> Result<String, AocError> {
+    let mut buffer = String::new();
+
+    match input_path(day, source) {
+        Some(path) => {
+            File::open(path)?.read_to_string(&mut buffer)?;
+        },
+        None => {
+            io::stdin().read_to_string(&mut buffer)?;
+        }
+    }
+
+    Ok(buffer)
+}
+
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+// The default hook prints the panic to stderr, which would make a mess of the
+// report. While a part is running we just note where the panic happened and
+// let call_part report it, anywhere else gets the default behaviour.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING_PANICS.with(|catching| catching.get()) {
+                let location = info
+                    .location()
+                    .map(|location| format!("{}:{}:{}", location.file(), location.line(), location.column()));
+                PANIC_LOCATION.with(|last| *last.borrow_mut() = location);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn panic_error(payload: Box<dyn Any + Send>) -> AocError {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    };
+
+    AocError::Panic {
+        message,
+        location: PANIC_LOCATION.with(|last| last.borrow_mut().take())
+    }
+}
+
+// Runs some of a solution, turning any panic into an AocError::Panic so that
+// one bad day can't take down the rest of the run.
+fn catch_panics<T, F>(f: F) -> Result<T, AocError>
+    where F: FnOnce() -> Result<T, AocError>
+{
+    install_panic_hook();
+
+    CATCHING_PANICS.with(|catching| catching.set(true));
+    // Nothing a solution touches outlives a panic, the whole day is dropped.
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANICS.with(|catching| catching.set(false));
+
+    result.unwrap_or_else(|payload| Err(panic_error(payload)))
+}
+
+pub fn call_part(solution: PartFn, input: &str) -> Result<Answer, AocError> {
+    catch_panics(|| solution(input))
+}
+
+pub fn call_parse<'a>(day: &Day, input: &'a str) -> Result<Box<dyn Parsed + 'a>, AocError> {
+    catch_panics(|| (day.parse)(input)).map_err(|error| day.locate(error))
+}
+
+pub fn call_parsed(parsed: &dyn Parsed, part: Part) -> Result<Answer, AocError> {
+    catch_panics(|| parsed.part(part))
+}
+
+// Times f and, with the counting allocator, what it allocated.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration, Option<Memory>) {
+    let ((result, duration), memory) = memory::measure(|| {
+        let start = Instant::now();
+        let result = f();
+        (result, start.elapsed())
+    });
+    (result, duration, memory)
+}
+
+fn run_parsed(parsed: &dyn Parsed, part: Part) -> PartReport {
+    let (result, duration, memory) = measure(|| call_parsed(parsed, part));
+    PartReport { memory, ..PartReport::new(result, duration) }
+}
+
+fn selected(parts: Parts) -> impl Iterator<Item = Part> {
+    IntoIterator::into_iter([(parts.one(), Part::One), (parts.two(), Part::Two)])
+        .filter(|(selected, _)| *selected)
+        .map(|(_, part)| part)
+}
+
+fn set_part(report: &mut DayReport, part: Part, part_report: PartReport) {
+    match part {
+        Part::One => report.part_one = Some(part_report),
+        Part::Two => report.part_two = Some(part_report)
+    }
+}
+
+fn run_input(day: &Day, input: &str, parts: Parts) -> Result<DayReport, AocError> {
+    let (parsed, parse, parse_memory) = measure(|| call_parse(day, input));
+    let parsed = parsed?;
+    let mut report = DayReport { parse, parse_memory, part_one: None, part_two: None };
+
+    for part in selected(parts) {
+        set_part(&mut report, part, run_parsed(&*parsed, part));
+    }
+
+    Ok(report)
+}
+
+// Every problem the day's validator finds with its input, or None if the day
+// doesn't have a validator.
+pub fn validate(day: &Day, source: &InputSource) -> Result<Option<Vec<AocError>>, AocError> {
+    let input = read_input(day, source)?;
+    let problems = catch_panics(|| Ok((day.validate)(input.trim())))?;
+
+    Ok(problems.map(|problems| problems.into_iter().map(|problem| day.locate(problem)).collect()))
+}
+
+pub fn run(day: &Day, source: &InputSource, parts: Parts) -> Result<DayReport, AocError> {
+    let buffer = read_input(day, source)?;
+    run_input(day, buffer.trim(), parts)
+}
+
+enum Phase {
+    Parse(Result<(Duration, Option<Memory>), AocError>),
+    Part(PartReport)
+}
+
+// Runs the day on its own thread so that we can stop waiting for it, with
+// each phase sent back as it finishes so the timeout applies to the parse and
+// each part separately. A phase that times out is abandoned rather than
+// killed, it'll keep spinning in the background until the process exits, and
+// any parts after it never get to run.
+fn run_with_timeout(day: &Day, input: Arc<str>, parts: Parts, timeout: Option<Duration>) -> Result<DayReport, AocError> {
+    let timeout = match timeout {
+        None => return run_input(day, &input, parts),
+        Some(timeout) => timeout
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let parse = day.parse;
+    thread::spawn(move || {
+        let (parsed, duration, memory) = measure(|| catch_panics(|| parse(&input)));
+        let parsed = match parsed {
+            Ok(parsed) => {
+                let _ = sender.send(Phase::Parse(Ok((duration, memory))));
+                parsed
+            },
+            Err(error) => {
+                let _ = sender.send(Phase::Parse(Err(error)));
+                return;
+            }
+        };
+
+        for part in selected(parts) {
+            if sender.send(Phase::Part(run_parsed(&*parsed, part))).is_err() {
+                break;
+            }
+        }
+    });
+
+    let exited = || AocError::Misc("Day exited without a result".to_string());
+    let mut report = match receiver.recv_timeout(timeout) {
+        Ok(Phase::Parse(parse)) => {
+            let (parse, parse_memory) = parse.map_err(|error| day.locate(error))?;
+            DayReport { parse, parse_memory, part_one: None, part_two: None }
+        },
+        Ok(Phase::Part(_)) => return Err(exited()),
+        Err(RecvTimeoutError::Timeout) => return Err(AocError::Timeout(timeout)),
+        Err(RecvTimeoutError::Disconnected) => return Err(exited())
+    };
+
+    let mut timed_out = None;
+    for part in selected(parts) {
+        let part_report = match (timed_out, receiver.recv_timeout(timeout)) {
+            (Some(earlier), _) => PartReport::new(
+                Err(AocError::Misc(format!("Not run, part {} timed out", earlier))),
+                Duration::new(0, 0)
+            ),
+            (None, Ok(Phase::Part(part_report))) => part_report,
+            (None, Err(RecvTimeoutError::Timeout)) => {
+                timed_out = Some(if part == Part::One { "one" } else { "two" });
+                PartReport::new(Err(AocError::Timeout(timeout)), timeout)
+            },
+            (None, _) => PartReport::new(Err(exited()), Duration::new(0, 0))
+        };
+        set_part(&mut report, part, part_report);
+    }
+
+    Ok(report)
+}
+
+// Runs every selected day across a pool of `jobs` worker threads. A day's
+// parts share its parsed input, so they run one after the other on the same
+// worker. Reports are handed to `on_report` in the same order as `days`, each
+// one as soon as it and every day before it have finished. Parsing or parts
+// that run longer than `timeout` are reported as AocError::Timeout.
+pub fn run_days<F>(days: Vec<(Day, Parts)>, source: &InputSource, jobs: usize, timeout: Option<Duration>, mut on_report: F)
+    where F: FnMut(Day, Result<DayReport, AocError>)
+{
+    let (job_sender, job_receiver) = mpsc::channel::<(usize, Day, Parts, Arc<str>)>();
+    let (result_sender, result_receiver) = mpsc::channel::<(usize, Day, Result<DayReport, AocError>)>();
+
+    let mut pending: Vec<Option<(Day, Result<DayReport, AocError>)>> = Vec::with_capacity(days.len());
+    let mut job_count = 0;
+
+    for (index, (day, parts)) in days.into_iter().enumerate() {
+        match read_input(&day, source) {
+            Ok(buffer) => {
+                // The receiver can't have gone away yet, we're holding it.
+                job_sender.send((index, day, parts, Arc::from(buffer.trim()))).unwrap();
+                job_count += 1;
+                pending.push(None);
+            },
+            Err(error) => pending.push(Some((day, Err(error))))
+        }
+    }
+
+    // Dropping the sender lets the workers' recv fail once the queue is empty.
+    drop(job_sender);
+
+    let job_receiver = Arc::new(Mutex::new(job_receiver));
+    for _ in 0..jobs.clamp(1, job_count.max(1)) {
+        let job_receiver = job_receiver.clone();
+        let result_sender = result_sender.clone();
+
+        thread::spawn(move || loop {
+            let (index, day, parts, input) = match job_receiver.lock().unwrap().recv() {
+                Ok(job) => job,
+                Err(_) => break
+            };
+
+            let report = run_with_timeout(&day, input, parts, timeout);
+            if result_sender.send((index, day, report)).is_err() {
+                break;
+            }
+        });
+    }
+    drop(result_sender);
+
+    let mut next = 0;
+    loop {
+        while next < pending.len() && pending[next].is_some() {
+            let (day, report) = pending[next].take().unwrap();
+            on_report(day, report);
+            next += 1;
+        }
+
+        if next >= pending.len() {
+            break;
+        }
+
+        match result_receiver.recv() {
+            Ok((index, day, report)) => pending[index] = Some((day, report)),
+            Err(_) => break
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use crate::client::test::temp_dir;
+    use crate::years::Solution;
+
+    struct Words;
+
+    impl Solution for Words {
+        type Parsed<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+            Ok(input.split_whitespace().collect())
+        }
+
+        fn part_one(words: &Vec<&str>) -> Result<Answer, AocError> {
+            Ok(words.len().into())
+        }
+
+        fn part_two(words: &Vec<&str>) -> Result<Answer, AocError> {
+            thread::sleep(Duration::from_millis(500));
+            Ok(words.join("").into())
+        }
+    }
+
+    fn words_day() -> Day {
+        Day::from_solution::<Words>(2020, "0", "Words", &[])
+    }
+
+    #[test]
+    fn parts_share_parsed_input() {
+        let report = run_input(&words_day(), "a b c", Parts::Both).unwrap();
+        assert_eq!(report.part_one.unwrap().result.unwrap(), Answer::Int(3));
+        assert_eq!(report.part_two.unwrap().result.unwrap(), Answer::from("abc"));
+    }
+
+    #[test]
+    fn timeouts_apply_to_each_phase() {
+        let input: Arc<str> = Arc::from("a b c");
+        let report = run_with_timeout(&words_day(), input, Parts::Both, Some(Duration::from_millis(100))).unwrap();
+        assert_eq!(report.part_one.unwrap().result.unwrap(), Answer::Int(3));
+        assert!(matches!(report.part_two.unwrap().result, Err(AocError::Timeout(_))));
+    }
+
+    #[test]
+    fn reports_come_back_in_selection_order() {
+        let dir = temp_dir("run-days");
+        fs::create_dir_all(dir.join("2020")).unwrap();
+
+        // Day 1 sleeps through its part two, so with more than one worker the
+        // days after it finish first.
+        let mut days = Vec::new();
+        for day in 1..=4 {
+            fs::write(dir.join("2020").join(format!("{}.txt", day)), "a ".repeat(day)).unwrap();
+            let parts = if day == 1 { Parts::Both } else { Parts::One };
+            days.push((Day { day: day.to_string(), ..words_day() }, parts));
+        }
+
+        let mut reports = Vec::new();
+        run_days(days, &InputSource::Dir(dir.clone()), 4, None, |day, report| {
+            let words = report.unwrap().part_one.unwrap().result.unwrap();
+            reports.push((day.day, words));
+        });
+
+        assert_eq!(reports, vec![
+            ("1".to_string(), Answer::Int(1)),
+            ("2".to_string(), Answer::Int(2)),
+            ("3".to_string(), Answer::Int(3)),
+            ("4".to_string(), Answer::Int(4))
+        ]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn slices_past_end(input: &str) -> Result<Answer, AocError> {
+        Ok(input[..7].into())
+    }
+
+    #[test]
+    fn panics_become_errors() {
+        match call_part(slices_past_end, "abc") {
+            Err(AocError::Panic { message, location }) => {
+                assert!(message.contains("out of bounds"));
+                assert!(location.unwrap().starts_with("src/runner.rs:"));
+            },
+            _ => panic!("Expected a panic error")
+        }
+
+        // And the next call isn't confused by the last one.
+        assert_eq!(call_part(slices_past_end, "abcdefgh").unwrap(), Answer::from("abcdefg"));
+    }
+
+    #[test]
+    fn answers_are_formatted_by_kind() {
+        let number: Result<Answer, AocError> = Ok(Answer::from(42));
+        assert_eq!(format_answer(&number), format!("{}42", " ".repeat(38)));
+
+        let text: Result<Answer, AocError> = Ok(Answer::from("abc"));
+        assert_eq!(format_answer(&text), format!("abc{}", " ".repeat(37)));
+
+        let missing: Result<Answer, AocError> = Ok(Answer::NotImplemented);
+        assert!(format_answer(&missing).starts_with("-- not implemented --"));
+
+        let art: Result<Answer, AocError> = Ok(Answer::Art(vec!["#.".to_string(), ".#".to_string()]));
+        assert!(!format_answer(&art).contains('\n'));
+    }
+}
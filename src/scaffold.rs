@@ -0,0 +1,228 @@
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use crate::aoc_error::AocError;
+use crate::runner::year_path;
+
+// Day modules are named after their day spelled out, one.rs to twentyfive.rs.
+const ONES: [&str; 10] = ["", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const TEENS: [&str; 10] = [
+    "ten", "eleven", "twelve", "thirteen", "fourteen",
+    "fifteen", "sixteen", "seventeen", "eighteen", "nineteen"
+];
+
+// The not yet written days are registered with this name, `new` is allowed to
+// replace them.
+const STUB_NAME: &str = "pub const NAME: &str = \"Unknown\";";
+
+pub fn module_name(day: u32) -> Option<String> {
+    match day {
+        1..=9 => Some(ONES[day as usize].to_string()),
+        10..=19 => Some(TEENS[day as usize - 10].to_string()),
+        20..=25 => Some(format!("twenty{}", ONES[day as usize - 20])),
+        _ => None
+    }
+}
+
+fn module_day(module: &str) -> Option<u32> {
+    (1..=25).find(|&day| module_name(day).as_deref() == Some(module))
+}
+
+pub fn render(name: &str) -> String {
+    TEMPLATE.replace("{name}", &name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+const TEMPLATE: &str = r#"use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::years::{Example, Solution};
+
+pub const NAME: &str = "{name}";
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, AocError> {
+        Ok(input)
+    }
+
+    fn part_one(_input: &&str) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_two(_input: &&str) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+}
+
+pub const EXAMPLES: &[Example] = &[];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // Paste the example from the puzzle description here. Once it passes it
+    // belongs in EXAMPLES, so `aoc20 examples` keeps checking it.
+    const EXAMPLE: &str = "
+";
+
+    #[test]
+    #[ignore = "no example yet"]
+    fn example_part_one() {
+        assert_eq!(solve_part_one::<Puzzle>(EXAMPLE.trim()).unwrap().to_line(), "");
+    }
+}
+"#;
+
+// Adds the module to a year's mod.rs, both the `pub mod` list and the
+// day_opts! invocation, keeping each in day order.
+pub fn register(mod_rs: &str, day: u32) -> Result<String, AocError> {
+    let module = module_name(day).ok_or_else(|| AocError::Misc(format!("Bad day {}", day)))?;
+    let mut lines: Vec<String> = mod_rs.lines().map(String::from).collect();
+
+    let mod_line = format!("pub mod {};", module);
+    if !lines.iter().any(|line| line.trim() == mod_line) {
+        let mods: Vec<(usize, u32)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let module = line.trim().strip_prefix("pub mod ")?.strip_suffix(';')?;
+                Some((i, module_day(module)?))
+            })
+            .collect();
+
+        let index = match mods.iter().find(|(_, other)| *other > day) {
+            Some((i, _)) => *i,
+            None => mods.last().map(|(i, _)| i + 1).unwrap_or(0)
+        };
+        lines.insert(index, mod_line);
+    }
+
+    let entry = format!("\"{}\" {}", day, module);
+    let entries: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.trim().trim_end_matches(',');
+            let (_, module) = line.strip_prefix('"')?.split_once("\" ")?;
+            Some((i, module_day(module)?))
+        })
+        .collect();
+
+    if entries.iter().any(|(_, other)| *other == day) {
+        return Ok(lines.join("\n") + "\n");
+    }
+
+    let indent = |i: usize| {
+        let line = &lines[i];
+        line[..line.len() - line.trim_start().len()].to_string()
+    };
+
+    match (entries.iter().find(|(_, other)| *other > day), entries.last()) {
+        (Some((i, _)), _) => {
+            let line = format!("{}{},", indent(*i), entry);
+            lines.insert(*i, line);
+        },
+        // Going on the end, the old last entry needs a comma now.
+        (None, Some((i, _))) => {
+            let line = format!("{}{}", indent(*i), entry);
+            lines[*i].push(',');
+            lines.insert(i + 1, line);
+        },
+        (None, None) => return Err(AocError::Misc("Couldn't find the day_opts! entries".to_string()))
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Writes src/years/y<year>/<module>.rs from the template, registers it and
+// makes an empty input file, returning the files it touched. A day that's
+// still the "Unknown" stub gets replaced, a real one is left alone.
+pub fn new_day(src: &Path, inputs: &Path, year: u32, day: u32, name: &str) -> Result<Vec<PathBuf>, AocError> {
+    let module = module_name(day).ok_or_else(|| AocError::Misc(format!("Bad day {}", day)))?;
+    let year_dir = src.join("years").join(format!("y{}", year));
+    let mod_path = year_dir.join("mod.rs");
+    let day_path = year_dir.join(format!("{}.rs", module));
+
+    let mod_rs = fs::read_to_string(&mod_path)
+        .map_err(|_| AocError::Misc(format!("Couldn't read {}", mod_path.display())))?;
+
+    if let Ok(existing) = fs::read_to_string(&day_path) {
+        if !existing.contains(STUB_NAME) {
+            return Err(AocError::Misc(format!("{} already exists", day_path.display())));
+        }
+    }
+
+    fs::write(&day_path, render(name))?;
+    fs::write(&mod_path, register(&mod_rs, day)?)?;
+
+    // Leaves an input that's already been fetched alone.
+    let input_path = year_path(inputs, year, &format!("{}.txt", day));
+    if let Some(parent) = input_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(&input_path)?;
+
+    Ok(vec![day_path, mod_path, input_path])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MOD_RS: &str = "pub mod one;
+pub mod three;
+
+use crate::years::Day;
+
+pub fn get_day(day: &str) -> Option<Day> {
+    day_opts! {
+        2020, day
+
+        \"1\" one,
+        \"3\" three
+    }
+}
+";
+
+    #[test]
+    fn module_names() {
+        assert_eq!(module_name(1).unwrap(), "one");
+        assert_eq!(module_name(13).unwrap(), "thirteen");
+        assert_eq!(module_name(20).unwrap(), "twenty");
+        assert_eq!(module_name(24).unwrap(), "twentyfour");
+        assert_eq!(module_name(26), None);
+    }
+
+    #[test]
+    fn registers_in_order() {
+        let registered = register(&register(MOD_RS, 2).unwrap(), 4).unwrap();
+        assert_eq!(registered, "pub mod one;
+pub mod two;
+pub mod three;
+pub mod four;
+
+use crate::years::Day;
+
+pub fn get_day(day: &str) -> Option<Day> {
+    day_opts! {
+        2020, day
+
+        \"1\" one,
+        \"2\" two,
+        \"3\" three,
+        \"4\" four
+    }
+}
+");
+
+        assert_eq!(register(&registered, 3).unwrap(), registered);
+    }
+
+    #[test]
+    fn template_escapes_name() {
+        assert!(render("Say \"hi\"").contains("pub const NAME: &str = \"Say \\\"hi\\\"\";"));
+    }
+}
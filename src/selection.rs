@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use crate::aoc_error::AocError;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Parts {
+    Both,
+    One,
+    Two
+}
+
+impl Parts {
+    pub fn one(&self) -> bool {
+        *self != Parts::Two
+    }
+
+    pub fn two(&self) -> bool {
+        *self != Parts::One
+    }
+
+    fn union(self, other: Parts) -> Parts {
+        if self == other { self } else { Parts::Both }
+    }
+}
+
+// A list of days to run, written like "1-5,9,17:2". Each comma separated item
+// is a day or inclusive range of days, optionally followed by :1 or :2 to only
+// run that part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection(pub Vec<(u32, Parts)>);
+
+impl Selection {
+    pub fn all() -> Selection {
+        Selection((1..=25).map(|day| (day, Parts::Both)).collect())
+    }
+
+    fn add(&mut self, day: u32, parts: Parts) {
+        match self.0.iter_mut().find(|(selected, _)| *selected == day) {
+            Some((_, selected_parts)) => *selected_parts = selected_parts.union(parts),
+            None => self.0.push((day, parts))
+        }
+    }
+}
+
+// Advent only runs for 25 days, checking that here keeps a range like 1-400000
+// from being expanded a day at a time. The commands that take a single day
+// use this too.
+pub fn parse_day(s: &str) -> Result<u32, AocError> {
+    match s.trim().parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(AocError::Misc(format!("Bad day {}", s)))
+    }
+}
+
+impl FromStr for Selection {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut selection = Selection(Vec::new());
+
+        for item in s.split(',') {
+            let (days, parts) = match item.split_once(':') {
+                None => (item, Parts::Both),
+                Some((days, "1")) => (days, Parts::One),
+                Some((days, "2")) => (days, Parts::Two),
+                Some((_, part)) => return Err(AocError::Misc(format!("Bad part {}", part)))
+            };
+
+            let (first, last) = match days.split_once('-') {
+                None => (parse_day(days)?, parse_day(days)?),
+                Some((first, last)) => (parse_day(first)?, parse_day(last)?)
+            };
+
+            if first > last {
+                return Err(AocError::Misc(format!("Backwards day range {}", days)));
+            }
+
+            for day in first..=last {
+                selection.add(day, parts);
+            }
+        }
+
+        Ok(selection)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ranges_lists_and_parts() {
+        let selection: Selection = "1-3,9,17:2".parse().unwrap();
+        assert_eq!(selection.0, vec![
+            (1, Parts::Both),
+            (2, Parts::Both),
+            (3, Parts::Both),
+            (9, Parts::Both),
+            (17, Parts::Two)
+        ]);
+    }
+
+    #[test]
+    fn repeated_days_merge() {
+        let selection: Selection = "15:1,15:1,16:2,16:1".parse().unwrap();
+        assert_eq!(selection.0, vec![(15, Parts::One), (16, Parts::Both)]);
+    }
+
+    #[test]
+    fn bad_selections() {
+        assert!("".parse::<Selection>().is_err());
+        assert!("5-3".parse::<Selection>().is_err());
+        assert!("4:3".parse::<Selection>().is_err());
+        assert!("x".parse::<Selection>().is_err());
+        assert!("0".parse::<Selection>().is_err());
+        assert!("26".parse::<Selection>().is_err());
+        assert!("1-400000".parse::<Selection>().is_err());
+    }
+}
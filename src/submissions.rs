@@ -0,0 +1,180 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::aoc_error::AocError;
+use crate::client::{Client, Outcome};
+use crate::runner::year_path;
+use crate::years::Part;
+
+// Every answer sent to the site is logged in submissions/<year>/<day>.txt
+// (see year_path), one per line with the part, when it was sent, what the
+// site said and the answer itself:
+//
+//     1 1607058000 too-low 1234
+//     1 1607058075 correct 514579
+//
+// The answer goes last since it's the only field that could have spaces in it.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: Part,
+    pub time: u64,
+    pub outcome: Outcome,
+    pub answer: String
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub submissions: Vec<Submission>
+}
+
+fn log_path(dir: &Path, year: u32, day: u32) -> PathBuf {
+    year_path(dir, year, &format!("{}.txt", day))
+}
+
+fn format_part(part: Part) -> &'static str {
+    match part {
+        Part::One => "1",
+        Part::Two => "2"
+    }
+}
+
+impl Submission {
+    fn parse(line: &str) -> Result<Submission, AocError> {
+        let bad_line = || AocError::Misc(format!("Bad submission line {}", line));
+
+        let mut fields = line.splitn(4, ' ');
+        let part = match fields.next() {
+            Some("1") => Part::One,
+            Some("2") => Part::Two,
+            _ => return Err(bad_line())
+        };
+        let time = fields.next().ok_or_else(bad_line)?.parse()?;
+        let outcome = fields.next().and_then(Outcome::from_token).ok_or_else(bad_line)?;
+        let answer = fields.next().ok_or_else(bad_line)?.to_string();
+
+        Ok(Submission { part, time, outcome, answer })
+    }
+
+    fn to_line(&self) -> String {
+        format!("{} {} {} {}\n", format_part(self.part), self.time, self.outcome.token(), self.answer)
+    }
+}
+
+impl SubmissionLog {
+    pub fn parse(s: &str) -> Result<SubmissionLog, AocError> {
+        let submissions = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Submission::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(SubmissionLog { submissions })
+    }
+
+    pub fn load(dir: &Path, year: u32, day: u32) -> Result<SubmissionLog, AocError> {
+        match fs::read_to_string(log_path(dir, year, day)) {
+            Ok(s) => SubmissionLog::parse(&s),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(error) => Err(error.into())
+        }
+    }
+
+    // The log is only ever added to, so new submissions are appended rather
+    // than rewriting the whole file.
+    pub fn append(dir: &Path, year: u32, day: u32, submission: &Submission) -> Result<(), AocError> {
+        let path = log_path(dir, year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(submission.to_line().as_bytes())?;
+
+        Ok(())
+    }
+
+    // An earlier submission of this answer that the site passed judgement on.
+    pub fn verdict(&self, part: Part, answer: &str) -> Option<&Submission> {
+        self.submissions
+            .iter()
+            .find(|submission| submission.part == part && submission.answer == answer && submission.outcome.is_verdict())
+    }
+}
+
+// Submits an answer unless the log says it's been judged before, in which case
+// the earlier submission comes back as an error. Whatever the site says is
+// added to the log.
+pub fn submit(client: &Client, dir: &Path, year: u32, day: u32, part: Part, answer: &str) -> Result<Outcome, AocError> {
+    let log = SubmissionLog::load(dir, year, day)?;
+    if let Some(previous) = log.verdict(part, answer) {
+        return Err(AocError::Misc(format!(
+            "{} was already submitted for part {}: {}",
+            answer,
+            format_part(part),
+            previous.outcome
+        )));
+    }
+
+    let outcome = client.submit(year, day, part, answer)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0);
+    let submission = Submission { part, time, outcome: outcome.clone(), answer: answer.to_string() };
+    SubmissionLog::append(dir, year, day, &submission)?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test::{serve, temp_dir};
+    use std::time::Duration;
+
+    #[test]
+    fn parse_log() {
+        let log = SubmissionLog::parse("1 1607058000 too-low 1234\n2 1607058075 correct a,b c\n").unwrap();
+        assert_eq!(log.submissions.len(), 2);
+        assert_eq!(log.submissions[0].outcome, Outcome::TooLow);
+        assert_eq!(log.submissions[1].part, Part::Two);
+        assert_eq!(log.submissions[1].answer, "a,b c");
+        assert_eq!(log.submissions[1].to_line(), "2 1607058075 correct a,b c\n");
+
+        assert!(SubmissionLog::parse("3 1607058000 correct 1").is_err());
+        assert!(SubmissionLog::parse("1 1607058000 maybe 1").is_err());
+    }
+
+    #[test]
+    fn never_resubmits_wrong_answers() {
+        let (base_url, server) = serve(vec![
+            (200, "<article><p>You gave an answer too recently. You have 30s left to wait.</p></article>"),
+            (200, "<article><p>That's not the right answer; your answer is too high.</p></article>")
+        ]);
+        let client = Client::new(&base_url, "abc123");
+        let dir = temp_dir("submit");
+
+        // Being rate limited isn't a verdict, so that one goes through again.
+        assert_eq!(
+            submit(&client, &dir, 2020, 1, Part::One, "999").unwrap(),
+            Outcome::RateLimited(Some(Duration::from_secs(30)))
+        );
+        assert_eq!(submit(&client, &dir, 2020, 1, Part::One, "999").unwrap(), Outcome::TooHigh);
+
+        // The server only has two responses, this has to be answered from the log.
+        match submit(&client, &dir, 2020, 1, Part::One, "999") {
+            Err(AocError::Misc(message)) => assert!(message.contains("too high")),
+            other => panic!("Expected the logged verdict, got {:?}", other)
+        }
+
+        let log = SubmissionLog::load(&dir, 2020, 1).unwrap();
+        assert_eq!(log.submissions.len(), 2);
+
+        server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+// Each year's solutions live in their own module tree, years/y<year>, with a
+// get_day function built from day_opts! below.
+
+use crate::{Answer, AocError};
+use crate::rng::Rng;
+
+// A day's solution. The input is parsed once, with its own timing, and both
+// parts work from the parsed value. Parsed can borrow from the input, which is
+// why it takes a lifetime.
+pub trait Solution {
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError>;
+    fn part_one(parsed: &Self::Parsed<'_>) -> Result<Answer, AocError>;
+    fn part_two(parsed: &Self::Parsed<'_>) -> Result<Answer, AocError>;
+
+    // Checks the input for anything the solution relies on without checking
+    // itself, reporting every problem found rather than stopping at the first.
+    // None for days that don't assume anything worth checking.
+    fn validate(_input: &str) -> Option<Vec<AocError>> {
+        None
+    }
+
+    // Makes up an input with an answer to both parts, for stress testing and
+    // benchmarking past the size of the real one. Size is roughly how many
+    // of the input's records to make, lines for most days, see each day for
+    // what it means there. None for days without a generator.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    // Makes up an input the puzzle allows but the parts assume they won't
+    // get, to see what their shortcuts make of it. Not every part will have
+    // an answer. None for days whose parts don't assume anything.
+    fn generate_tricky(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    // The parts again, done the obvious way from the raw input with none of
+    // the shortcuts, to check the real ones against on generated inputs.
+    // Being slow is fine. Not implemented for days without them.
+    fn reference_one(_input: &str) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn reference_two(_input: &str) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+}
+
+// A whole part, from the raw input to the answer.
+pub type PartFn = fn(&str) -> Result<Answer, AocError>;
+
+// The runner holds on to parsed inputs through this so that days with
+// different Parsed types can share a registry.
+pub trait Parsed {
+    fn part(&self, part: Part) -> Result<Answer, AocError>;
+}
+
+struct ParsedInput<'a, S: Solution>(S::Parsed<'a>);
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn part(&self, part: Part) -> Result<Answer, AocError> {
+        match part {
+            Part::One => S::part_one(&self.0),
+            Part::Two => S::part_two(&self.0)
+        }
+    }
+}
+
+pub type Parser = for<'a> fn(&'a str) -> Result<Box<dyn Parsed + 'a>, AocError>;
+
+pub type Validator = fn(&str) -> Option<Vec<AocError>>;
+
+pub type Generator = fn(&mut Rng, usize) -> Option<String>;
+
+pub fn parse_input<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed + '_>, AocError> {
+    Ok(Box::new(ParsedInput::<S>(S::parse(input)?)))
+}
+
+pub fn solve_part_one<S: Solution>(input: &str) -> Result<Answer, AocError> {
+    S::part_one(&S::parse(input)?)
+}
+
+pub fn solve_part_two<S: Solution>(input: &str) -> Result<Answer, AocError> {
+    S::part_two(&S::parse(input)?)
+}
+
+pub const DEFAULT_YEAR: u32 = 2020;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two
+}
+
+// One of the worked examples from a puzzle description.
+#[derive(Debug, Copy, Clone)]
+pub struct Example {
+    pub part: Part,
+    pub input: &'static str,
+    pub expected: &'static str,
+    // Some puzzles use different constants in their examples than for the
+    // real input (day 9's preamble length), those examples point this at a
+    // version of the part using the example's constants.
+    pub solution: Option<PartFn>
+}
+
+impl Example {
+    pub const fn new(part: Part, input: &'static str, expected: &'static str) -> Example {
+        Example { part, input, expected, solution: None }
+    }
+
+    pub const fn with_solution(self, solution: PartFn) -> Example {
+        Example { solution: Some(solution), ..self }
+    }
+}
+
+#[derive(Clone)]
+pub struct Day {
+    pub year: u32,
+    pub day: String,
+    pub name: String,
+    pub parse: Parser,
+    // The parts again with their own parsing, for running one on its own.
+    pub part_one: PartFn,
+    pub part_two: PartFn,
+    pub validate: Validator,
+    pub generate: Generator,
+    pub generate_tricky: Generator,
+    pub reference_one: PartFn,
+    pub reference_two: PartFn,
+    pub examples: &'static [Example]
+}
+
+impl Day {
+    // For the registry's days, and the made up ones in tests.
+    pub fn from_solution<S: Solution + 'static>(year: u32, day: &str, name: &str, examples: &'static [Example]) -> Day {
+        Day {
+            year,
+            day: day.to_string(),
+            name: name.to_string(),
+            parse: parse_input::<S>,
+            part_one: solve_part_one::<S>,
+            part_two: solve_part_two::<S>,
+            validate: S::validate,
+            generate: S::generate,
+            generate_tricky: S::generate_tricky,
+            reference_one: S::reference_one,
+            reference_two: S::reference_two,
+            examples
+        }
+    }
+
+    pub fn solution(&self, part: Part) -> PartFn {
+        match part {
+            Part::One => self.part_one,
+            Part::Two => self.part_two
+        }
+    }
+
+    pub fn reference(&self, part: Part) -> PartFn {
+        match part {
+            Part::One => self.reference_one,
+            Part::Two => self.reference_two
+        }
+    }
+
+    // Parse errors don't know which day they came from until they're told.
+    pub fn locate(&self, error: AocError) -> AocError {
+        match self.day.parse() {
+            Ok(day) => error.in_day(day),
+            Err(_) => error
+        }
+    }
+}
+
+macro_rules! day_opts {
+    ( $year:literal, $target:ident $( $name:literal $module:ident ),* ) => {
+        match $target {
+            $(
+                $name => Some($crate::years::Day::from_solution::<$module::Puzzle>(
+                    $year, $name, $module::NAME, $module::EXAMPLES
+                )),
+            )*
+            _ => None
+        }
+    }
+}
+
+// These have to come after day_opts! for the macro to be in scope.
+pub mod y2020;
+
+pub fn years() -> Vec<u32> {
+    vec![2020]
+}
+
+pub fn get_day(year: u32, day: &str) -> Option<Day> {
+    match year {
+        2020 => y2020::get_day(day),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A generated input is only any use if the solutions can answer it, and
+    // the validators should have nothing to say about it either.
+    #[test]
+    fn generated_inputs_have_answers() {
+        for year in years() {
+            for day in (1..=25).filter_map(|day| get_day(year, &day.to_string())) {
+                // Small and only the one, since day 17 takes a while in a debug
+                // build whatever the size.
+                let input = match (day.generate)(&mut Rng::new(0), 3) {
+                    Some(input) => input,
+                    None => continue
+                };
+
+                let problems = (day.validate)(&input).unwrap_or_default();
+                assert!(problems.is_empty(), "{} day {}: {:?}", year, day.day, problems);
+                for part in IntoIterator::into_iter([Part::One, Part::Two]) {
+                    let answer = (day.solution(part))(&input);
+                    assert!(answer.is_ok(), "{} day {} {:?}: {:?}\n{}", year, day.day, part, answer, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generators_are_seeded() {
+        let day = get_day(2020, "7").unwrap();
+        let generate = |seed| (day.generate)(&mut Rng::new(seed), 50);
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+        assert_eq!(generate(1).unwrap().lines().count(), 50);
+
+        let day = get_day(2020, "1").unwrap();
+        assert!((day.generate)(&mut Rng::new(0), 10).is_some());
+    }
+
+    #[test]
+    fn days_without_validators_have_nothing_to_say() {
+        let day = get_day(2020, "1").unwrap();
+        assert!((day.validate)("1721").is_none());
+    }
+}
@@ -0,0 +1,352 @@
+use std::str::FromStr;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Handheld Halting";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Opcode {
+    Acc,
+    Jump,
+    Noop
+}
+
+use Opcode::*;
+
+#[derive(Clone)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub arg: isize,
+    visited: bool
+}
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((opcode, arg)) = s.split_once(" ") {
+            let opcode = match opcode {
+                "acc" => Ok(Acc),
+                "jmp" => Ok(Jump),
+                "nop" => Ok(Noop),
+                _ => Err(AocError::parse("Bad opcode", s, opcode))
+            }?;
+
+            // Arguments are kept to an i32 so that the accumulator can't
+            // overflow, each instruction only runs once before the machine
+            // stops.
+            let arg = arg
+                .parse::<i32>()
+                .map_err(|_| AocError::parse("Bad argument", s, arg))?;
+
+            Ok(Instruction {
+                opcode,
+                arg: arg as isize,
+                visited: false
+            })
+        } else {
+            Err(AocError::parse("Bad instruction", s, s))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitReason {
+    InfiniteLoop,
+    Completed
+}
+
+use ExitReason::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MachineState {
+    Running,
+    Exited
+}
+
+use MachineState::*;
+
+#[derive(Clone)]
+pub struct Machine {
+    pub instructions: Vec<Instruction>,
+    pointer: usize,
+    pub accumulator: isize,
+    state: MachineState,
+    pub exit_reason: Option<ExitReason>
+}
+
+impl FromStr for Machine {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let instructions = s
+            .lines()
+            .map(|line| line.parse().map_err(|error: AocError| error.within(s, line)))
+            .collect::<Result<Vec<Instruction>, AocError>>()?;
+
+        Ok(Machine {
+            instructions,
+            pointer: 0,
+            accumulator: 0,
+            state: Running,
+            exit_reason: None
+        })
+    }
+}
+
+impl Machine {
+    pub fn step(&mut self) {
+        if self.state == Exited {
+            return;
+        }
+
+        if self.pointer >= self.instructions.len() {
+            self.state = Exited;
+            self.exit_reason = Some(Completed);
+            return;
+        }
+
+        let instruction = &mut self.instructions[self.pointer];
+
+        if instruction.visited {
+            self.state = Exited;
+            self.exit_reason = Some(InfiniteLoop);
+            return;
+        }
+
+        match instruction.opcode {
+            Acc => self.accumulator += instruction.arg,
+            Jump => {
+                // Subtract one here to compensate for the unconditional
+                // pointer increment below.
+                let pointer = self.pointer as isize + instruction.arg - 1;
+                self.pointer = pointer as usize;
+            }
+            Noop => { }
+        };
+
+        instruction.visited = true;
+        // A jmp +0 leaves the pointer wrapped around to -1 before this, so this
+        // has to wrap back too.
+        self.pointer = self.pointer.wrapping_add(1);
+    }
+
+    pub fn eval(&mut self) {
+        loop {
+            self.step();
+            if self.state == Exited {
+                break;
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for instruction in self.instructions.iter_mut() {
+            instruction.visited = false;
+        }
+
+        self.accumulator = 0;
+        self.pointer = 0;
+        self.state = Running;
+        self.exit_reason = None;
+    }
+
+    pub fn swap_opcode_at(&mut self, line: usize) {
+        let instruction = &mut self.instructions[line];
+
+        match instruction.opcode {
+            Acc => {},
+            Jump => instruction.opcode = Noop,
+            Noop => instruction.opcode = Jump
+        }
+    }
+}
+
+// Runs the program from the top, for the references. True along with the
+// accumulator if it ran off the end, false if it started repeating itself.
+fn run_program(program: &[(&str, i64)]) -> Result<(bool, i64), AocError> {
+    let mut seen = vec![false; program.len()];
+    let mut pointer = 0i64;
+    let mut accumulator = 0i64;
+
+    while 0 <= pointer && (pointer as usize) < program.len() {
+        let i = pointer as usize;
+        if seen[i] {
+            return Ok((false, accumulator));
+        }
+        seen[i] = true;
+
+        match program[i] {
+            ("acc", arg) => {
+                accumulator = accumulator.checked_add(arg).ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+                pointer += 1;
+            },
+            ("jmp", arg) => pointer += arg,
+            _ => pointer += 1
+        }
+    }
+
+    Ok((true, accumulator))
+}
+
+fn read_program(input: &str) -> Result<Vec<(&str, i64)>, AocError> {
+    input
+        .lines()
+        .map(|line| match line.split_once(' ') {
+            Some((opcode @ ("acc" | "jmp" | "nop"), arg)) => {
+                let arg = arg.parse::<i32>().map_err(|_| AocError::parse("Bad argument", input, arg))?;
+                Ok((opcode, arg as i64))
+            },
+            _ => Err(AocError::parse("Bad instruction", input, line))
+        })
+        .collect()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Machine;
+
+    fn parse(input: &str) -> Result<Machine, AocError> {
+        input.parse()
+    }
+
+    // Both parts run the machine, so they each get their own copy.
+    fn part_one(machine: &Machine) -> Result<Answer, AocError> {
+        let mut machine = machine.clone();
+        machine.eval();
+
+        Ok(machine.accumulator.into())
+    }
+
+    fn part_two(machine: &Machine) -> Result<Answer, AocError> {
+        let mut machine = machine.clone();
+
+        // Lets brute force our way through all possible instructions
+        // I'm pretty sure you could solve this statically, but this runs in
+        // less than 500us on my machine. so eh.
+        for line in 0..machine.instructions.len() {
+            if machine.instructions[line].opcode != Acc {
+                machine.swap_opcode_at(line);
+                machine.eval();
+
+                if machine.exit_reason == Some(Completed) {
+                    return Ok(machine.accumulator.into());
+                }
+
+                machine.reset();
+                machine.swap_opcode_at(line);
+            }
+        }
+
+        Err(AocError::Misc("No opcode swap exits".to_string()))
+    }
+
+    // Size is how many instructions. The program is in two halves: the first
+    // only ever jumps forward within itself and ends with a jmp back to the
+    // start, the second runs off the end from anywhere in it. Turning that
+    // last jmp of the first half into a nop is the only fix, every other
+    // swap keeps the machine in the first half.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(2);
+        let looping = rng.between(1, size as u64 - 1) as usize;
+
+        let mut program = Vec::with_capacity(size);
+        let acc = |rng: &mut Rng| format!("acc {:+}", rng.between_signed(-50, 50));
+
+        // The looping half, 0 to looping. Nops only point somewhere in this
+        // half too, so swapping them can't escape.
+        while program.len() < looping {
+            let here = program.len() as i64;
+            let room = looping as i64 - here;
+            if room >= 2 && rng.chance(0.2) {
+                let skip = rng.between_signed(2, room.min(5));
+                program.push(format!("jmp {:+}", skip));
+                // Whatever gets skipped over never runs, it just has to stay
+                // in this half if it's ever swapped and run.
+                for _ in 1..skip {
+                    let here = program.len() as i64;
+                    let target = rng.between_signed(0, looping as i64) - here;
+                    program.push(match rng.below(3) {
+                        0 => acc(rng),
+                        1 => format!("jmp {:+}", target),
+                        _ => format!("nop {:+}", target)
+                    });
+                }
+            } else if rng.chance(0.7) {
+                program.push(acc(rng));
+            } else {
+                let target = rng.between_signed(0, looping as i64);
+                program.push(format!("nop {:+}", target - here));
+            }
+        }
+        program.push(format!("jmp {:+}", -(looping as i64)));
+
+        // The half that finishes, only ever moving forwards.
+        while program.len() < size {
+            let here = program.len() as i64;
+            let room = size as i64 - here;
+            program.push(match rng.below(4) {
+                0 | 1 => acc(rng),
+                2 => format!("jmp {:+}", rng.between_signed(1, room.min(5))),
+                _ => format!("nop {:+}", rng.between_signed(-50, 50))
+            });
+        }
+
+        Some(program.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let (_, accumulator) = run_program(&read_program(input)?)?;
+        Ok(accumulator.into())
+    }
+
+    // A fresh copy of the program for every swap.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let program = read_program(input)?;
+        for i in 0..program.len() {
+            let mut swapped = program.clone();
+            swapped[i].0 = match swapped[i].0 {
+                "jmp" => "nop",
+                "nop" => "jmp",
+                _ => continue
+            };
+
+            if let (true, accumulator) = run_program(&swapped)? {
+                return Ok(accumulator.into());
+            }
+        }
+
+        Err(AocError::Misc("No opcode swap exits".to_string()))
+    }
+}
+
+const EXAMPLE: &str = "
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "5"),
+    Example::new(Part::Two, EXAMPLE, "8")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // The accumulator used to overflow.
+    #[test]
+    fn huge_arguments_are_errors() {
+        assert!(solve_part_one::<Puzzle>("acc +9223372036854775807\nacc +1").is_err());
+    }
+}
\ No newline at end of file
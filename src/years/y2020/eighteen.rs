@@ -0,0 +1,372 @@
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Operation Order";
+
+pub fn tokenize(expression: &str) -> Vec<String> {
+    expression
+        // This is pretty janky, but in my defense I'm stealing bad ideas from
+        // the best https://norvig.com/lispy.html
+        .replace("(", "( ")
+        .replace(")", " )")
+        .split(" ")
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn matched_paren(tokens: &[String], start_paren: usize) -> Result<usize, AocError> {
+    if tokens.get(start_paren).map(String::as_str) != Some("(") {
+        return Err(AocError::Misc("Can't find matched paren without start paren".to_string()));
+    }
+
+    let mut depth = 1;
+    let mut index = start_paren;
+    while depth > 0 {
+        index += 1;
+        match tokens.get(index).map(String::as_str) {
+            Some("(") => depth += 1,
+            Some(")") => depth -= 1,
+            Some(_) => {},
+            None => return Err(AocError::Misc("Unmatched paren".to_string()))
+        }
+    }
+
+    Ok(index)
+}
+
+fn overflow() -> AocError {
+    AocError::Misc("Overflow".to_string())
+}
+
+// I'm sure it's possible to combine the part one and part two solutions more
+// by having two strategies for building an AST and then a single eval function
+// the evals the tree, but it's pretty late and I don't feel like it.
+
+fn eval_term_p1(tokens: &[String]) -> Result<(isize, &[String]), AocError> {
+    let first = tokens
+        .first()
+        .ok_or_else(|| AocError::Misc("Missing term".to_string()))?;
+
+    if first == "(" {
+        let end = matched_paren(tokens, 0)?;
+        let val = eval_expression_p1(&tokens[1..end])?;
+        Ok((val, &tokens[(end + 1)..]))
+    } else {
+        let constant = first.parse()?;
+        Ok((constant, &tokens[1..]))
+    }
+}
+
+pub fn eval_expression_p1(tokens: &[String]) -> Result<isize, AocError> {
+    let (mut value, mut tokens) = eval_term_p1(tokens)?;
+
+    while !tokens.is_empty() {
+        let op = &tokens[0];
+        let (right, remaining) = eval_term_p1(&tokens[1..])?;
+        tokens = remaining;
+
+        match op.as_str() {
+            "+" => value = value.checked_add(right).ok_or_else(overflow)?,
+            "*" => value = value.checked_mul(right).ok_or_else(overflow)?,
+            _ => return Err(AocError::Misc("Invalid operator".to_string()))
+        }
+    }
+
+    Ok(value)
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Term {
+    Constant(isize),
+    Add,
+    Mul
+}
+
+fn eval_term_p2(tokens: &[String]) -> Result<(Term, &[String]), AocError> {
+    if tokens[0] == "(" {
+        let end = matched_paren(tokens, 0)?;
+        let val = eval_expression_p2(&tokens[1..end])?;
+        Ok((Term::Constant(val), &tokens[(end + 1)..]))
+    } else if tokens[0] == "+" {
+        Ok((Term::Add, &tokens[1..]))
+    } else if tokens[0] == "*" {
+        Ok((Term::Mul, &tokens[1..]))
+    } else {
+        let val = tokens[0].parse()?;
+        Ok((Term::Constant(val), &tokens[1..]))
+    }
+}
+
+pub fn eval_expression_p2(tokens: &[String]) -> Result<isize, AocError> {
+    let mut terms = Vec::new();
+    let mut tokens = tokens;
+    while !tokens.is_empty() {
+        let (term, remaining) = eval_term_p2(tokens)?;
+        terms.push(term);
+        tokens = remaining;
+    }
+
+    // This is a pretty elaborate way to avoid making an AST, but it's pretty
+    // late at night.
+
+    let mut added_terms = Vec::new();
+    let mut i = 0;
+    while i < terms.len() {
+        let term = &terms[i];
+        match term {
+            Term::Constant(x) => added_terms.push(*x),
+            Term::Mul => { },
+            Term::Add => {
+                let left = added_terms
+                    .pop()
+                    .ok_or_else(|| AocError::Misc("Bad expression".to_string()))?;
+                match terms.get(i + 1) {
+                    Some(Term::Constant(right)) => {
+                        added_terms.push(left.checked_add(*right).ok_or_else(overflow)?);
+                    },
+                    _ => return Err(AocError::Misc("Bad expression".to_string()))
+                }
+                i += 1;
+            }
+        }
+
+        i += 1;
+    }
+
+    added_terms
+        .iter()
+        .try_fold(1isize, |product, x| product.checked_mul(*x))
+        .ok_or_else(overflow)
+}
+
+// Two to five terms, each either a digit or, up to two deep, a bracketed
+// expression of its own.
+fn random_expression(rng: &mut Rng, depth: usize) -> String {
+    let mut expression = String::new();
+    for i in 0..rng.between(2, 5) {
+        if i > 0 {
+            expression.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+        if depth < 2 && rng.chance(0.3) {
+            expression.push_str(&format!("({})", random_expression(rng, depth + 1)));
+        } else {
+            expression.push_str(&rng.between(1, 9).to_string());
+        }
+    }
+    expression
+}
+
+// A precedence climbing parser straight over the characters, for the
+// references. Addition binds tighter than multiplication if add_first,
+// otherwise they're equal and go left to right.
+fn reference_eval(expression: &[u8], at: &mut usize, min_precedence: u8, add_first: bool) -> Result<isize, AocError> {
+    let skip_spaces = |at: &mut usize| {
+        while expression.get(*at) == Some(&b' ') {
+            *at += 1;
+        }
+    };
+
+    skip_spaces(at);
+    let mut value = match expression.get(*at) {
+        Some(b'(') => {
+            *at += 1;
+            let value = reference_eval(expression, at, 0, add_first)?;
+            skip_spaces(at);
+            if expression.get(*at) != Some(&b')') {
+                return Err(AocError::Misc("Unmatched paren".to_string()));
+            }
+            *at += 1;
+            value
+        },
+        Some(c) if c.is_ascii_digit() => {
+            let start = *at;
+            while expression.get(*at).is_some_and(u8::is_ascii_digit) {
+                *at += 1;
+            }
+            String::from_utf8_lossy(&expression[start..*at]).parse()?
+        },
+        _ => return Err(AocError::Misc("Missing term".to_string()))
+    };
+
+    loop {
+        skip_spaces(at);
+        let op = match expression.get(*at) {
+            Some(op @ (b'+' | b'*')) => *op,
+            _ => return Ok(value)
+        };
+        let precedence = if add_first && op == b'+' { 2 } else { 1 };
+        if precedence < min_precedence {
+            return Ok(value);
+        }
+
+        *at += 1;
+        let right = reference_eval(expression, at, precedence + 1, add_first)?;
+        value = match op {
+            b'+' => value.checked_add(right),
+            _ => value.checked_mul(right)
+        }
+        .ok_or_else(overflow)?;
+    }
+}
+
+fn reference_sum(input: &str, add_first: bool) -> Result<isize, AocError> {
+    let mut sum = 0isize;
+    for line in input.lines() {
+        let mut at = 0;
+        let value = reference_eval(line.as_bytes(), &mut at, 0, add_first)?;
+        if at != line.len() {
+            return Err(AocError::parse("Unexpected character", input, &line[at..]));
+        }
+        sum = sum.checked_add(value).ok_or_else(overflow)?;
+    }
+    Ok(sum)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    // One list of tokens per line.
+    type Parsed<'a> = Vec<Vec<String>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<String>>, AocError> {
+        Ok(input.lines().map(tokenize).collect())
+    }
+
+    fn part_one(expressions: &Vec<Vec<String>>) -> Result<Answer, AocError> {
+        let mut answer = 0;
+        for tokens in expressions {
+            answer = eval_expression_p1(tokens)?.checked_add(answer).ok_or_else(overflow)?;
+        }
+
+        Ok(answer.into())
+    }
+
+    fn part_two(expressions: &Vec<Vec<String>>) -> Result<Answer, AocError> {
+        let mut answer = 0;
+        for tokens in expressions {
+            answer = eval_expression_p2(tokens)?.checked_add(answer).ok_or_else(overflow)?;
+        }
+
+        Ok(answer.into())
+    }
+
+    // Size is how many expressions. Any that come to more than 10^12 either
+    // way are made again, so the sums stay inside an isize.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let small = |value: Result<isize, AocError>| matches!(value, Ok(value) if value <= 1_000_000_000_000);
+
+        let lines: Vec<String> = (0..size)
+            .map(|_| loop {
+                let expression = random_expression(rng, 0);
+                let tokens = tokenize(&expression);
+                if small(eval_expression_p1(&tokens)) && small(eval_expression_p2(&tokens)) {
+                    break expression;
+                }
+            })
+            .collect();
+
+        Some(lines.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_sum(input, false)?.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_sum(input, true)?.into())
+    }
+}
+
+const EXAMPLE: &str = "
+1 + 2 * 3 + 4 * 5 + 6
+1 + (2 * 3) + (4 * (5 + 6))
+2 * 3 + (4 * 5)
+5 + (8 * 3 + 9 + 3 * 4 * 3)
+5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "26457"),
+    Example::new(Part::Two, EXAMPLE, "694173")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    #[test]
+    fn one_op_add_p1() {
+        let s = "1 + 2";
+        let value = eval_expression_p1(&tokenize(s)).unwrap();
+        assert_eq!(value, 3);
+    }
+
+    #[test]
+    fn root_parens_p1() {
+        let s = "(2 * 3)";
+        let value = eval_expression_p1(&tokenize(s)).unwrap();
+        assert_eq!(value, 6);
+    }
+
+    #[test]
+    fn interior_parens_p1() {
+        let s = "1 + (2 + 3) * 4";
+        let value = eval_expression_p1(&tokenize(s)).unwrap();
+        assert_eq!(value, 24);
+    }
+
+    #[test]
+    fn nested_parens_p1() {
+        let s = "((1 * 2) + (3 + 4)) + 5";
+        let value = eval_expression_p1(&tokenize(s)).unwrap();
+        assert_eq!(value, 14);
+    }
+
+    #[test]
+    fn one_op_add_p2() {
+        let s = "1 + 2";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 3);
+    }
+
+    #[test]
+    fn add_precedence_p2() {
+        let s = "1 + 2 * 3 + 4 * 5 + 6";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 231);
+    }
+
+    #[test]
+    fn examples_p2() {
+        let s = "1 + (2 * 3) + (4 * (5 + 6))";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 51);
+
+        let s = "2 * 3 + (4 * 5)";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 46);
+
+        let s = "5 + (8 * 3 + 9 + 3 * 4 * 3)";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 1445);
+
+        let s = "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 669060);
+
+        let s = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let value = eval_expression_p2(&tokenize(s)).unwrap();
+        assert_eq!(value, 23340);
+    }
+
+    // An unmatched paren used to index past the last token, and big
+    // products overflowed.
+    #[test]
+    fn bad_expressions_are_errors() {
+        assert!(solve_part_one::<Puzzle>("(1").is_err());
+        assert!(solve_part_one::<Puzzle>("99999999999 * 99999999999").is_err());
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashSet;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Seating System";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum State {
+    Floor,
+    Empty,
+    Occupied
+}
+
+use State::*;
+
+impl State {
+    pub fn from_char(c: char) -> Result<Self, AocError> {
+        match c {
+            '.' => Ok(Floor),
+            'L' => Ok(Empty),
+            '#' => Ok(Occupied),
+            _ => Err(AocError::Misc(format!("Invalid state char {}", c)))
+        }
+    }
+}
+
+struct Positions {
+    width: usize,
+    height: usize,
+    row: usize,
+    col: usize
+}
+
+impl Iterator for Positions {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.height || self.col >= self.width {
+            None
+        } else {
+            let pos = (self.row, self.col);
+
+            self.col += 1;
+            if self.col == self.width {
+                self.col = 0;
+                self.row += 1;
+            }
+
+            Some(pos)
+        }
+    }
+}
+
+impl Positions {
+    fn new(width: usize, height: usize) -> Positions {
+        Positions {
+            width,
+            height,
+            row: 0,
+            col: 0
+        }
+    }
+}
+
+const DIRECTIONS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1),
+                                         ( 0, -1),          ( 0, 1),
+                                         ( 1, -1), (1,  0), ( 1, 1)];
+
+pub fn neighbors_p1(row: usize, col: usize, width: usize, height: usize, tiles: &[State]) -> Vec<usize> {
+    let mut neighbors = Vec::new();
+    for dir in DIRECTIONS.iter() {
+        let other_row = row as isize + dir.0;
+        let other_col = col as isize + dir.1;
+
+        if other_row >= 0 && other_col >= 0 && other_row < height as isize && other_col < width as isize {
+            let other_index = other_row as usize * width + other_col as usize;
+            let other = tiles[other_index];
+
+            if other == Empty {
+                neighbors.push(other_index);
+            }
+        }
+    }
+
+    neighbors
+}
+
+pub fn neighbors_p2(row: usize, col: usize, width: usize, height: usize, tiles: &[State]) -> Vec<usize> {
+    let mut neighbors = Vec::new();
+
+    for direction in DIRECTIONS.iter() {
+        let mut other_row = row as isize + direction.0;
+        let mut other_col = col as isize + direction.1;
+        loop {
+            if other_row < 0 || other_col < 0 || other_row >= height as isize || other_col >= width as isize {
+                break;
+            }
+
+            let other_index = other_row as usize * width + other_col as usize;
+            let other = tiles[other_index];
+            if other == Empty {
+                neighbors.push(other_index);
+                break;
+            }
+
+            other_row += direction.0;
+            other_col += direction.1;
+        }
+    }
+
+    neighbors
+}
+
+// The seats as read from the input, before either part's idea of who can see
+// who has been worked out.
+#[derive(Debug, Clone)]
+pub struct Layout {
+    pub tiles: Vec<State>,
+    pub width: usize,
+    pub height: usize
+}
+
+impl Layout {
+    pub fn read(s: &str) -> Result<Self, AocError> {
+        let rows: Vec<&str> = s.strip_suffix('\n').unwrap_or(s).split('\n').collect();
+        let width = rows[0].chars().count();
+
+        let mut tiles = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            // Positions are worked out from the width, a ragged row would
+            // put every seat after it in the wrong place.
+            if row.chars().count() != width {
+                return Err(AocError::parse("Every row has to be as wide as the first", s, row));
+            }
+            for c in row.chars() {
+                tiles.push(State::from_char(c)?);
+            }
+        }
+
+        Ok(Layout {
+            tiles,
+            width,
+            height: rows.len()
+        })
+    }
+}
+
+pub struct WaitingArea {
+    tiles: Vec<State>,
+    neighbors: Vec<Vec<usize>>,
+    width: usize,
+    height: usize
+}
+
+impl WaitingArea {
+    pub fn new<F>(layout: &Layout, neighbor_fn: F) -> Self
+        where F: Fn(usize, usize, usize, usize, &[State]) -> Vec<usize>
+    {
+        let Layout { tiles, width, height } = layout.clone();
+
+        let mut neighbors = Vec::with_capacity(tiles.len());
+        for (row, col) in Positions::new(width, height) {
+            neighbors.push(neighbor_fn(row, col, width, height, &tiles));
+        }
+
+        WaitingArea {
+            tiles,
+            neighbors,
+            width,
+            height
+        }
+    }
+
+    pub fn read<F>(s: &str, neighbor_fn: F) -> Result<Self, AocError>
+        where F: Fn(usize, usize, usize, usize, &[State]) -> Vec<usize>
+    {
+        Ok(WaitingArea::new(&Layout::read(s)?, neighbor_fn))
+    }
+}
+
+impl WaitingArea {
+    fn positions(&self) -> Positions {
+        Positions::new(self.width, self.height)
+    }
+
+    fn state_at(&self, row: isize, col: isize) -> State {
+        // Everything outside the area bounds can be treated as a empty for
+        // the purposes of "how many full seats are adjacent to me".
+        if row < 0 || col < 0 || row >= self.height as isize || col >= self.width as isize {
+            Empty
+        } else {
+            let index = row as usize * self.width + col as usize;
+            self.tiles[index]
+        }
+    }
+
+    // Only tick flips seats, with the index of one it's just looked at.
+    fn flip(&mut self, index: usize) {
+        if self.tiles[index] == Occupied {
+            self.tiles[index] = Empty;
+        } else if self.tiles[index] == Empty {
+            self.tiles[index] = Occupied;
+        }
+    }
+
+    // True if anyone moved.
+    pub fn tick(&mut self, leave_threshold: usize) -> bool {
+        let mut to_flip = Vec::new();
+
+        // TODO: Iterators
+        for (row, col) in self.positions() {
+            let here = self.state_at(row as isize, col as isize);
+
+            if here == Floor {
+                continue;
+            }
+
+            let mut occupied = 0;
+            for other_index in &self.neighbors[row * self.width + col] {
+                if self.tiles[*other_index] == Occupied {
+                    occupied += 1;
+                }
+            }
+
+            if here == Empty && occupied == 0 ||
+               here == Occupied && occupied >= leave_threshold{
+                to_flip.push(row * self.width + col);
+            }
+        }
+
+        let changed = !to_flip.is_empty();
+        for index in to_flip {
+            self.flip(index);
+        }
+        changed
+    }
+
+    // Ticks until nobody moves, and then how many seats are taken. Some
+    // layouts never settle, flipping back and forth between the same states
+    // forever. Brent's cycle detection catches that holding on to just one
+    // earlier state, swapped for the current one whenever the ticks since it
+    // reach the next power of two, so however long the cycle is it comes
+    // back round to the held state before long.
+    pub fn settle(&mut self, leave_threshold: usize) -> Result<usize, AocError> {
+        let mut held = self.tiles.clone();
+        let mut since_held = 0;
+        let mut power = 1;
+        while self.tick(leave_threshold) {
+            if self.tiles == held {
+                return Err(AocError::Misc("Seats never settle".to_string()));
+            }
+
+            since_held += 1;
+            if since_held == power {
+                held.clone_from(&self.tiles);
+                since_held = 0;
+                power *= 2;
+            }
+        }
+
+        Ok(self.total_occupied())
+    }
+
+    pub fn total_occupied(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|&&state| state == Occupied)
+            .count()
+    }
+}
+
+// Runs the rules on a plain grid of the input's characters until nothing
+// changes, or it's back to a grid it's been before, looking each seat's
+// neighbors up afresh every round. Part one stops looking after one step in
+// each direction, part two carries on past the floor.
+fn reference_settle(input: &str, far_sighted: bool, leave_at: usize) -> Result<usize, AocError> {
+    let mut grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    if let Some(c) = grid.iter().flatten().find(|c| !".L#".contains(**c)) {
+        return Err(AocError::Misc(format!("Invalid state char {}", c)));
+    }
+
+    let seen = |grid: &Vec<Vec<char>>, row: usize, col: usize| {
+        let mut occupied = 0;
+        for (d_row, d_col) in DIRECTIONS.iter() {
+            let (mut r, mut c) = (row as isize + d_row, col as isize + d_col);
+            while r >= 0 && c >= 0 && (r as usize) < grid.len() && (c as usize) < grid[r as usize].len() {
+                match grid[r as usize][c as usize] {
+                    '#' => { occupied += 1; break; },
+                    'L' => break,
+                    _ if !far_sighted => break,
+                    _ => {}
+                }
+                r += d_row;
+                c += d_col;
+            }
+        }
+        occupied
+    };
+
+    let mut earlier = HashSet::new();
+    loop {
+        let mut next = grid.clone();
+        for row in 0..grid.len() {
+            for col in 0..grid[row].len() {
+                match (grid[row][col], seen(&grid, row, col)) {
+                    ('L', 0) => next[row][col] = '#',
+                    ('#', occupied) if occupied >= leave_at => next[row][col] = 'L',
+                    _ => {}
+                }
+            }
+        }
+
+        if next == grid {
+            return Ok(grid.iter().flatten().filter(|c| **c == '#').count());
+        }
+        if !earlier.insert(grid) {
+            return Err(AocError::Misc("Seats never settle".to_string()));
+        }
+        grid = next;
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Layout;
+
+    fn parse(input: &str) -> Result<Layout, AocError> {
+        Layout::read(input)
+    }
+
+    fn part_one(layout: &Layout) -> Result<Answer, AocError> {
+        Ok(WaitingArea::new(layout, neighbors_p1).settle(4)?.into())
+    }
+
+    fn part_two(layout: &Layout) -> Result<Answer, AocError> {
+        Ok(WaitingArea::new(layout, neighbors_p2).settle(5)?.into())
+    }
+
+    // Size is how many rows, the layout stays 98 wide like the real one.
+    // Random layouts don't always settle, a patch of seats can fill up and
+    // empty out again forever. In part one that only depends on the seats
+    // right around the patch, so the rows come in bands split up by a row of
+    // floor, each band made again until it settles on its own. Part two sees
+    // past the floor and has to be checked as a whole, but it hardly ever
+    // goes round in circles.
+    //
+    // Checking part two means settling the whole layout, which takes longer
+    // the more rows there are, since a change can be seen from anywhere
+    // along the line of sight and ripples all the way down. There's no
+    // splitting that up, only seats block the view and they'd move too, so
+    // sizes stop at MAX_ROWS. That's about twenty times the real input and
+    // a few seconds to make in a release build, with part two about as long
+    // again.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const BAND: usize = 8;
+        const MAX_ROWS: usize = 2000;
+
+        let random_row = |rng: &mut Rng| -> String {
+            (0..98).map(|_| if rng.chance(0.75) { 'L' } else { '.' }).collect()
+        };
+        let settles = |rows: &[String], leave_threshold, neighbor_fn: fn(usize, usize, usize, usize, &[State]) -> Vec<usize>| {
+            Layout::read(&rows.join("\n"))
+                .is_ok_and(|layout| WaitingArea::new(&layout, neighbor_fn).settle(leave_threshold).is_ok())
+        };
+
+        let size = size.clamp(1, MAX_ROWS);
+        loop {
+            let mut rows = Vec::with_capacity(size);
+            while rows.len() < size {
+                let height = BAND.min(size - rows.len());
+                let band = loop {
+                    let band: Vec<String> = (0..height).map(|_| random_row(rng)).collect();
+                    if settles(&band, 4, neighbors_p1) {
+                        break band;
+                    }
+                };
+                rows.extend(band);
+                if rows.len() < size {
+                    rows.push(".".repeat(98));
+                }
+            }
+
+            if settles(&rows, 5, neighbors_p2) {
+                return Some(rows.join("\n"));
+            }
+        }
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_settle(input, false, 4)?.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_settle(input, true, 5)?.into())
+    }
+}
+
+const EXAMPLE: &str = "
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "37"),
+    Example::new(Part::Two, EXAMPLE, "26")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::{solve_part_one, solve_part_two};
+
+    // A short row used to index outside the seats.
+    #[test]
+    fn ragged_rows_are_errors() {
+        assert!(solve_part_one::<Puzzle>("L.\nL").is_err());
+    }
+
+    // Every seat here has four neighbors, so they all fill up and then all
+    // empty again, forever. Part one used to stop only once the number of
+    // taken seats stayed the same, which never happens here, so it hung.
+    #[test]
+    fn seats_that_never_settle_are_errors() {
+        let input = ".LL.\nLLLL\nLLLL\n.LL.";
+        assert!(solve_part_one::<Puzzle>(input).is_err());
+        assert!(reference_settle(input, false, 4).is_err());
+        assert_eq!(solve_part_two::<Puzzle>(input).unwrap(), Answer::Int(8));
+    }
+}
\ No newline at end of file
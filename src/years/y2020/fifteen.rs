@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::num::ParseIntError;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Rambunctious Recitation";
+
+pub struct Game {
+    turn: u32,
+    // We're effectively using this Vec as a hashmap, just without the hashing.
+    // A lot of the slots in this vec never get used, and part two's comes to
+    // 114MiB (build with --features count-allocations to see it), but it runs
+    // ~5 times faster.
+    history: Vec<u32>
+}
+
+impl Game {
+    pub fn new(max_num: u32) -> Game {
+        Game {
+            turn: 0,
+            history: vec![0; max_num as usize]
+        }
+    }
+
+    pub fn speak(&mut self, number: u32) -> u32 {
+        self.turn += 1;
+        let number = number as usize;
+
+        if self.history[number] > 0 {
+            let next = self.turn - self.history[number];
+            self.history[number] = self.turn;
+            next
+        } else {
+            self.history[number] = self.turn;
+            0
+        }
+    }
+
+    pub fn play(&mut self, seed: &[u32], turns: u32) -> u32 {
+        let mut next = 0;
+
+        for number in seed {
+            next = self.speak(*number);
+        }
+
+        while self.turn < (turns - 1) {
+            next = self.speak(next);
+        }
+
+        next
+    }
+}
+
+const PART_ONE_TURNS: u32 = 2020;
+const PART_TWO_TURNS: u32 = 30000000;
+
+// Numbers said after the starting ones are always less than the number of
+// turns, but the history also has to have room for the starting numbers.
+fn play(seed: &[u32], turns: u32) -> u32 {
+    let max_num = seed
+        .iter()
+        .map(|n| n + 1)
+        .max()
+        .unwrap_or(0)
+        .max(turns);
+
+    Game::new(max_num).play(seed, turns)
+}
+
+// The game spoken out turn by turn, remembering when each number was last
+// said in a map. If the starting numbers already go past the turn asked for,
+// the answer is one of them.
+fn reference_game(input: &str, turns: usize) -> Result<u64, AocError> {
+    let seed = input
+        .split(',')
+        .map(|s| s.parse())
+        .collect::<Result<Vec<u64>, ParseIntError>>()?;
+    if seed.is_empty() || seed.len() >= turns {
+        return seed
+            .get(turns - 1)
+            .copied()
+            .ok_or_else(|| AocError::Misc("No starting numbers".to_string()));
+    }
+
+    let mut last_said: HashMap<u64, usize> = HashMap::new();
+    for (turn, n) in seed[..seed.len() - 1].iter().enumerate() {
+        last_said.insert(*n, turn + 1);
+    }
+
+    let mut spoken = seed[seed.len() - 1];
+    for turn in seed.len()..turns {
+        let next = match last_said.get(&spoken) {
+            Some(before) => (turn - before) as u64,
+            None => 0
+        };
+        last_said.insert(spoken, turn);
+        spoken = next;
+    }
+
+    Ok(spoken)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Vec<u32>, AocError> {
+        let seed = input
+            .split(',')
+            .map(|s| s.parse())
+            .collect::<Result<Vec<u32>, ParseIntError>>()?;
+
+        // Bigger starting numbers would only make the history bigger than
+        // part two's already is.
+        if let Some(too_big) = seed.iter().find(|n| **n >= PART_TWO_TURNS) {
+            return Err(AocError::Misc(format!("Starting number {} is too big", too_big)));
+        }
+
+        Ok(seed)
+    }
+
+    fn part_one(seed: &Vec<u32>) -> Result<Answer, AocError> {
+        let last = play(seed, PART_ONE_TURNS);
+
+        Ok(last.into())
+    }
+
+    fn part_two(seed: &Vec<u32>) -> Result<Answer, AocError> {
+        let last = play(seed, PART_TWO_TURNS);
+
+        Ok(last.into())
+    }
+
+    // Size is how many starting numbers, at most 1000 since part one only
+    // has 2020 turns. They're all different, like the real ones.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let count = size.clamp(1, 1000);
+        let mut numbers: Vec<usize> = (0..count * 3).collect();
+        rng.shuffle(&mut numbers);
+
+        let numbers: Vec<String> = numbers[..count].iter().map(usize::to_string).collect();
+        Some(numbers.join(","))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_game(input, PART_ONE_TURNS as usize)?.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_game(input, PART_TWO_TURNS as usize)?.into())
+    }
+}
+
+// Part two has the same seven examples, but each one takes a while to play
+// out to thirty million turns so only the first is here.
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, "0,3,6", "436"),
+    Example::new(Part::One, "1,3,2", "1"),
+    Example::new(Part::One, "2,1,3", "10"),
+    Example::new(Part::One, "1,2,3", "27"),
+    Example::new(Part::One, "2,3,1", "78"),
+    Example::new(Part::One, "3,2,1", "438"),
+    Example::new(Part::One, "3,1,2", "1836"),
+    Example::new(Part::Two, "0,3,6", "175594")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // The history used to be sized by the turns alone, a bigger starting
+    // number ran off the end of it.
+    #[test]
+    fn huge_starting_numbers_are_errors() {
+        assert!(solve_part_one::<Puzzle>("30000000").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Binary Boarding";
+
+#[derive(Debug)]
+pub struct BoardingPass {
+    // These could be stored as u8 (row is 0..127) and col is (0..7) but when
+    // we find the seat id we have to cast them anyway, this just lets us not
+    // have to cast.
+    pub row: u16,
+    pub col: u16
+}
+
+fn bit_string(s: &str, zero: char, one: char) -> Result<u16, AocError> {
+    s.chars()
+        .enumerate()
+        .map(|(i, c)| (s.len() - 1 - i, c))
+        .try_fold(0, |acc, (shift, c)| {
+            let bit = match c {
+                _ if c == zero => { 0 },
+                _ if c == one => { 1 },
+                _ => { return Err(AocError::Misc("Bad bit string char".to_string())) }
+            };
+
+            Ok(acc + (bit << shift))
+        })
+}
+
+impl FromStr for BoardingPass {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (row, col) = match (s.len(), s.get(..7), s.get(7..)) {
+            (10, Some(row), Some(col)) => (row, col),
+            _ => return Err(AocError::parse("Boarding passes are 10 characters", s, s))
+        };
+        let row = bit_string(row, 'F', 'B')?;
+        let col = bit_string(col, 'L', 'R')?;
+
+        Ok(BoardingPass { row, col })
+    }
+}
+
+impl BoardingPass {
+    pub fn seat_id(&self) -> u16 {
+        self.row * 8 + self.col
+    }
+}
+
+// A seat ID read one letter at a time, for the references.
+fn reference_seat_id(pass: &str) -> Result<u32, AocError> {
+    if pass.chars().count() != 10 {
+        return Err(AocError::parse("Boarding passes are 10 characters", pass, pass));
+    }
+
+    let mut id = 0;
+    for (i, c) in pass.chars().enumerate() {
+        let bit = match (i < 7, c) {
+            (true, 'F') | (false, 'L') => 0,
+            (true, 'B') | (false, 'R') => 1,
+            _ => return Err(AocError::Misc("Bad bit string char".to_string()))
+        };
+        id = id * 2 + bit;
+    }
+
+    Ok(id)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<BoardingPass>;
+
+    fn parse(input: &str) -> Result<Vec<BoardingPass>, AocError> {
+        input
+            .lines()
+            .map(|line| line.parse())
+            .collect()
+    }
+
+    fn part_one(passes: &Vec<BoardingPass>) -> Result<Answer, AocError> {
+        let max_id = passes
+            .iter()
+            .map(|pass| pass.seat_id())
+            .max()
+            .ok_or_else(|| AocError::Misc("No boarding passes".to_string()))?;
+
+        Ok(max_id.into())
+    }
+
+    fn part_two(passes: &Vec<BoardingPass>) -> Result<Answer, AocError> {
+        let mut seat_ids: Vec<u16> = passes
+            .iter()
+            .map(|pass| pass.seat_id())
+            .collect();
+
+        seat_ids.sort_unstable();
+
+        seat_ids
+            .iter()
+            .zip(seat_ids.iter().skip(1))
+            // Iterator borrows, why you gotta do it to me
+            .filter(|(prev, seat)| **prev + 1 != **seat)
+            .map(|(_, seat)| (seat - 1).into())
+            .next()
+            .ok_or_else(|| AocError::Misc("No valid seat".to_string()))
+    }
+
+    // Size is how many boarding passes, at most 1000 since that's about all
+    // the seats there are. They fill a block of seats apart from one in the
+    // middle.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let count = size.clamp(2, 1000) as u64;
+        let first = rng.between(1, 1023 - count);
+        let missing = rng.between(first + 1, first + count - 1);
+
+        let mut passes: Vec<String> = (first..=first + count)
+            .filter(|seat| *seat != missing)
+            .map(|seat| {
+                let bits = |width: u32, value: u64, zero: char, one: char| -> String {
+                    (0..width).rev().map(|bit| if value >> bit & 1 == 1 { one } else { zero }).collect()
+                };
+                bits(7, seat / 8, 'F', 'B') + &bits(3, seat % 8, 'L', 'R')
+            })
+            .collect();
+        rng.shuffle(&mut passes);
+
+        Some(passes.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let mut highest = None;
+        for line in input.lines() {
+            highest = highest.max(Some(reference_seat_id(line)?));
+        }
+
+        Ok(highest.ok_or_else(|| AocError::Misc("No boarding passes".to_string()))?.into())
+    }
+
+    // Every seat in the plane, looking for a free one between two taken.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let taken = input
+            .lines()
+            .map(reference_seat_id)
+            .collect::<Result<Vec<u32>, AocError>>()?;
+
+        (1..1023)
+            .find(|id| !taken.contains(id) && taken.contains(&(id - 1)) && taken.contains(&(id + 1)))
+            .map(Answer::from)
+            .ok_or_else(|| AocError::Misc("No valid seat".to_string()))
+    }
+}
+
+const EXAMPLE: &str = "
+FBFBBFFRLR
+BFFFBBFRRR
+FFFBBBFRRR
+BBFFBBFRLL";
+
+// Part two has no example, it needs a nearly full plane.
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "820")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // Finding the highest seat used to unwrap on no seats at all.
+    #[test]
+    fn no_boarding_passes_is_an_error() {
+        assert!(solve_part_one::<Puzzle>("").is_err());
+    }
+}
\ No newline at end of file
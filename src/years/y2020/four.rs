@@ -0,0 +1,421 @@
+use std::str::FromStr;
+use std::collections::HashMap;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Passport Processing";
+
+pub struct Passport<'a> {
+    fields: HashMap<&'a str, &'a str>
+}
+
+impl<'a> Passport<'a> {
+    // This can't be a FromStr impl because the result borrows from the input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &'a str) -> Result<Passport<'a>, AocError> {
+        let fields = s.split_whitespace()
+            .map(|field| {
+                field
+                    .split_once(":")
+                    .ok_or_else(|| AocError::parse("Bad record", s, field))
+            })
+            .collect::<Result<HashMap<&str, &str>, AocError>>()?;
+
+        Ok(Passport { fields })
+    }
+}
+
+impl Passport<'_> {
+    // Part one only cares that the keys are there. There are no records with
+    // invalid keys, so we can jut check the size of the map.
+    pub fn part_one_valid(&self) -> bool {
+        self.fields.len() == 8 ||
+        (self.fields.len() == 7 && !self.fields.contains_key("cid"))
+    }
+
+    // TODO: If I make fields a more structured type I can make this validation
+    // a lot cleaner.
+    pub fn part_two_valid(&self) -> bool {
+        if self.fields.len() < 7 {
+            return false;
+        }
+
+        // No valid value has a + in it, but the number parsing below would
+        // take one as a sign. cid can be anything.
+        if self.fields.iter().any(|(key, value)| *key != "cid" && value.contains('+')) {
+            return false;
+        }
+
+        // byr (Birth Year) - four digits; at least 1920 and at most 2002.
+        if let Some(value) = self.fields.get("byr") {
+            match value.parse::<u16>() {
+                Err(_) => { return false; },
+                Ok(year) => {
+                    if !(1920..=2002).contains(&year) {
+                        return false;
+                    }
+                }
+            };
+        } else {
+            return false;
+        }
+
+        // iyr (Issue Year) - four digits; at least 2010 and at most 2020.
+        if let Some(value) = self.fields.get("iyr") {
+            match value.parse::<u16>() {
+                Err(_) => { return false; },
+                Ok(year) => {
+                    if !(2010..=2020).contains(&year) {
+                        return false;
+                    }
+                }
+            };
+        } else {
+            return false;
+        }
+
+        // eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
+        if let Some(value) = self.fields.get("eyr") {
+            match value.parse::<u16>() {
+                Err(_) => { return false; },
+                Ok(year) => {
+                    if !(2020..=2030).contains(&year) {
+                        return false;
+                    }
+                }
+            };
+        } else {
+            return false;
+        }
+
+        // hgt (Height) - a number followed by either cm or in:
+        //     If cm, the number must be at least 150 and at most 193.
+        //     If in, the number must be at least 59 and at most 76.
+        if let Some(value) = self.fields.get("hgt") {
+            let split = value.len().saturating_sub(2);
+            let (height, unit) = match (value.get(..split), value.get(split..)) {
+                (Some(height), Some(unit)) => (height, unit),
+                _ => { return false; }
+            };
+            let height: u16 = match height.parse() {
+                Err(_) => { return false; },
+                Ok(height) => height
+            };
+
+            // A height with no unit isn't either of them, and isn't valid.
+            let allowed = match unit {
+                "cm" => 150..=193,
+                "in" => 59..=76,
+                _ => { return false; }
+            };
+            if !allowed.contains(&height) {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        // hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
+        if let Some(value) = self.fields.get("hcl") {
+            if value.len() != 7 {
+                return false;
+            }
+
+            if !value.starts_with('#') {
+                return false;
+            }
+
+            if usize::from_str_radix(&value[1..], 16).is_err() {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        // ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+        if let Some(value) = self.fields.get("ecl") {
+            if !(value == &"amb" || value == &"blu" || value == &"brn" ||
+                 value == &"gry" || value == &"grn" || value == &"hzl" ||
+                 value == &"oth") {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        // pid (Passport ID) - a nine-digit number, including leading zeroes.
+        if let Some(value) = self.fields.get("pid") {
+            if value.len() != 9 {
+                return false;
+            }
+
+            if usize::from_str(value).is_err() {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        // cid (Country ID) - ignored, missing or not.
+
+        true
+    }
+}
+
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+// Between min and max digits.
+fn digits(rng: &mut Rng, min: u64, max: u64) -> String {
+    (0..rng.between(min, max)).map(|_| (b'0' + rng.between(0, 9) as u8) as char).collect()
+}
+
+fn hex_digits(rng: &mut Rng, min: u64, max: u64) -> String {
+    (0..rng.between(min, max)).map(|_| *rng.choose(b"0123456789abcdef") as char).collect()
+}
+
+// A value for one of the fields, either one part two accepts or something
+// close enough to it to need checking.
+fn field_value(rng: &mut Rng, field: &str, valid: bool) -> String {
+    match (field, valid) {
+        ("byr", true) => rng.between(1920, 2002).to_string(),
+        ("byr", false) if rng.chance(0.5) => format!("+{}", rng.between(1920, 2002)),
+        ("byr", false) => rng.between(1900, 2030).to_string(),
+        ("iyr", true) => rng.between(2010, 2020).to_string(),
+        ("iyr", false) => rng.between(2000, 2030).to_string(),
+        ("eyr", true) => rng.between(2020, 2030).to_string(),
+        ("eyr", false) => rng.between(2010, 2040).to_string(),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.between(150, 193)),
+        ("hgt", true) => format!("{}in", rng.between(59, 76)),
+        ("hgt", false) => format!("{}{}", rng.between(50, 200), rng.choose(&["cm", "in", ""])),
+        ("hcl", true) => format!("#{}", hex_digits(rng, 6, 6)),
+        ("hcl", false) if rng.chance(0.5) => hex_digits(rng, 6, 6),
+        ("hcl", false) if rng.chance(0.5) => format!("#+{}", hex_digits(rng, 5, 5)),
+        ("hcl", false) => format!("#{}", hex_digits(rng, 3, 8)),
+        ("ecl", true) => rng.choose(&EYE_COLORS).to_string(),
+        ("ecl", false) => rng.choose(&["amb", "grn", "gmt", "lzr", "xry", "zzz"]).to_string(),
+        ("pid", true) => digits(rng, 9, 9),
+        ("pid", false) if rng.chance(0.5) => format!("+{}", digits(rng, 8, 8)),
+        ("pid", false) => digits(rng, 7, 11),
+        _ => rng.between(100, 350).to_string()
+    }
+}
+
+// The references' reading of the batch, each passport's fields as key and
+// value pairs.
+fn read_passports(input: &str) -> Result<Vec<Vec<(&str, &str)>>, AocError> {
+    input
+        .split("\n\n")
+        .map(|record| {
+            record
+                .split_whitespace()
+                .map(|field| field.split_once(':').ok_or_else(|| AocError::parse("Bad record", input, field)))
+                .collect()
+        })
+        .collect()
+}
+
+fn has_required_fields(passport: &[(&str, &str)]) -> bool {
+    FIELDS
+        .iter()
+        .filter(|field| **field != "cid")
+        .all(|field| passport.iter().any(|(key, _)| key == field))
+}
+
+fn year_between(value: &str, min: u32, max: u32) -> bool {
+    value.len() == 4 &&
+        value.chars().all(|c| c.is_ascii_digit()) &&
+        value.parse().is_ok_and(|year: u32| min <= year && year <= max)
+}
+
+// Each of part two's rules as written in the puzzle.
+fn field_is_valid(key: &str, value: &str) -> bool {
+    match key {
+        "byr" => year_between(value, 1920, 2002),
+        "iyr" => year_between(value, 2010, 2020),
+        "eyr" => year_between(value, 2020, 2030),
+        "hgt" => {
+            let (number, min, max) = if let Some(number) = value.strip_suffix("cm") {
+                (number, 150, 193)
+            } else if let Some(number) = value.strip_suffix("in") {
+                (number, 59, 76)
+            } else {
+                return false;
+            };
+            !number.is_empty() &&
+                number.chars().all(|c| c.is_ascii_digit()) &&
+                number.parse().is_ok_and(|height: u32| min <= height && height <= max)
+        },
+        "hcl" => match value.strip_prefix('#') {
+            Some(color) => color.len() == 6 && color.chars().all(|c| "0123456789abcdef".contains(c)),
+            None => false
+        },
+        "ecl" => EYE_COLORS.contains(&value),
+        "pid" => value.len() == 9 && value.chars().all(|c| c.is_ascii_digit()),
+        _ => true
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<Passport<'a>>;
+
+    fn parse(input: &str) -> Result<Vec<Passport<'_>>, AocError> {
+        input
+            .split("\n\n")
+            .map(|record| Passport::from_str(record).map_err(|error| error.within(input, record)))
+            .collect()
+    }
+
+    fn part_one(passports: &Vec<Passport<'_>>) -> Result<Answer, AocError> {
+        let valid_count = passports
+            .iter()
+            .filter(|passport| passport.part_one_valid())
+            .count();
+
+        Ok(valid_count.into())
+    }
+
+    fn part_two(passports: &Vec<Passport<'_>>) -> Result<Answer, AocError> {
+        let valid_count = passports
+            .iter()
+            .filter(|passport| passport.part_two_valid())
+            .count();
+
+        Ok(valid_count.into())
+    }
+
+    // Size is how many passports. A few fields go missing and a few have
+    // values that are nearly right, so both parts have some to turn away.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let passports: Vec<String> = (0..size)
+            .map(|_| {
+                let mut fields = Vec::new();
+                for field in &FIELDS {
+                    if rng.chance(if *field == "cid" { 0.5 } else { 0.95 }) {
+                        let valid = rng.chance(0.9);
+                        fields.push(format!("{}:{}", field, field_value(rng, field, valid)));
+                    }
+                }
+                rng.shuffle(&mut fields);
+
+                let mut passport = String::new();
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+                    }
+                    passport.push_str(field);
+                }
+                passport
+            })
+            .collect();
+
+        Some(passports.join("\n\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let count = read_passports(input)?
+            .iter()
+            .filter(|passport| has_required_fields(passport))
+            .count();
+
+        Ok(count.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let count = read_passports(input)?
+            .iter()
+            .filter(|passport| has_required_fields(passport))
+            .filter(|passport| passport.iter().all(|(key, value)| field_is_valid(key, value)))
+            .count();
+
+        Ok(count.into())
+    }
+}
+
+const EXAMPLE: &str = "
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+const INVALID_EXAMPLE: &str = "
+eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+const VALID_EXAMPLE: &str = "
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "2"),
+    Example::new(Part::Two, INVALID_EXAMPLE, "0"),
+    Example::new(Part::Two, VALID_EXAMPLE, "4")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_two;
+
+    const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+
+    fn valid_with(field: &str, value: &str) -> bool {
+        let passport = VALID
+            .split(' ')
+            .map(|pair| if pair.starts_with(field) { format!("{}:{}", field, value) } else { pair.to_string() })
+            .collect::<Vec<_>>()
+            .join(" ");
+        solve_part_two::<Puzzle>(&passport).unwrap() == Answer::Int(1)
+    }
+
+    // Part two used to split 160 into a height of 1 and a unit of 60, and
+    // with a unit that was neither cm nor in it let any height through.
+    #[test]
+    fn heights_need_a_unit() {
+        assert!(valid_with("hgt", "160cm"));
+        assert!(!valid_with("hgt", "160"));
+        assert!(!valid_with("hgt", "160xx"));
+    }
+
+    // Part two used to let the number parsing take a + as the sign, so
+    // +1980 passed as a birth year and +12345678 as a passport ID.
+    #[test]
+    fn numbers_have_no_sign() {
+        assert!(!valid_with("byr", "+1980"));
+        assert!(!valid_with("pid", "+12345678"));
+    }
+}
\ No newline at end of file
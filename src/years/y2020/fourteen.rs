@@ -0,0 +1,451 @@
+use std::str::FromStr;
+use std::collections::HashMap;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Docking Data";
+
+#[derive(Debug)]
+pub struct MaskedAddresses {
+    base: u64,
+    floats: Vec<usize>,
+    n: usize
+}
+
+impl Iterator for MaskedAddresses {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n >= 2usize.pow(self.floats.len() as u32) {
+            None
+        } else {
+            let mut val = self.base;
+
+            for i in 0..self.floats.len() {
+                if (self.n & (1 << i)) != 0 {
+                    val += 1 << self.floats[i];
+                }
+            }
+
+            self.n += 1;
+
+            Some(val)
+        }
+    }
+}
+
+pub trait Bitmask {
+    fn apply_val(&self, x: u64) -> u64;
+    fn apply_mem(&self, x: u64) -> MaskedAddresses;
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ValueBitmask {
+    pub ones: u64,
+    pub zeros: u64
+}
+
+impl FromStr for ValueBitmask {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ones = 0;
+        let mut zeros = 0;
+        for (i, c) in s.chars().rev().enumerate() {
+            if c == '1' {
+                ones += 1 << i;
+                zeros += 1 << i;
+            } else if c == 'X' {
+                zeros += 1 << i;
+            }
+        }
+
+        Ok(ValueBitmask { ones, zeros })
+    }
+}
+
+impl Bitmask for ValueBitmask {
+    fn apply_val(&self, x: u64) -> u64 {
+        (x | self.ones) & self.zeros
+    }
+
+    fn apply_mem(&self, x: u64) -> MaskedAddresses {
+        MaskedAddresses {
+            base: x,
+            floats: vec![],
+            n: 0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemBitmask {
+    pub ones: u64,
+    pub floats: Vec<usize>
+}
+
+impl FromStr for MemBitmask {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let floats = s
+            .chars()
+            .rev()
+            .enumerate()
+            .filter(|(_, c)| c == &'X')
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut ones = 0;
+        for (i, c) in s.chars().rev().enumerate() {
+            if c == '1' {
+                ones += 1 << i;
+            }
+        }
+
+        Ok(MemBitmask { ones, floats })
+    }
+}
+
+impl Bitmask for MemBitmask {
+    fn apply_val(&self, x: u64) -> u64 {
+        x
+    }
+
+    fn apply_mem(&self, x: u64) -> MaskedAddresses {
+        let mask = u64::MAX;
+
+        let mut base = x | self.ones;
+        for bit in &self.floats {
+            base &= mask - (1 << bit);
+        }
+
+        MaskedAddresses {
+            base,
+            floats: self.floats.clone(),
+            n: 0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Instruction<T: FromStr<Err = AocError>> {
+    Mask(T),
+    Mem(u64, u64)
+}
+
+impl<T: FromStr<Err = AocError>> FromStr for Instruction<T> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(mask) = s.strip_prefix("mask = ") {
+            // The bitmasks shift by position, anything past 36 bits would
+            // overflow.
+            if mask.len() > 36 {
+                return Err(AocError::parse("Mask longer than 36 bits", s, mask));
+            }
+            if let Some((i, c)) = mask.char_indices().find(|(_, c)| !"01X".contains(*c)) {
+                return Err(AocError::parse("Bad mask bit", s, &mask[i..i + c.len_utf8()]));
+            }
+            Ok(Instruction::Mask(mask.parse()?))
+        } else if s.starts_with("mem") {
+            let tokens: Vec<&str> = s.split(&['[', ']'][..]).collect();
+
+            if tokens.len() != 3 {
+                Err(AocError::parse("Invalid mem instruction", s, s))
+            } else {
+                let addr = tokens[1]
+                    .parse()
+                    .map_err(|_| AocError::parse("Bad address", s, tokens[1]))?;
+                let value = tokens[2]
+                    .strip_prefix(" = ")
+                    .ok_or_else(|| AocError::parse("Invalid mem instruction", s, tokens[2]))?;
+                let value = value
+                    .parse()
+                    .map_err(|_| AocError::parse("Bad value", s, value))?;
+                Ok(Instruction::Mem(addr, value))
+            }
+        } else {
+            Err(AocError::parse("Invalid instruction", s, s))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<T: Bitmask + Clone + FromStr<Err = AocError>> {
+    pub instructions: Vec<Instruction<T>>,
+    pointer: usize,
+    pub memory: HashMap<u64, u64>,
+    mask: T
+}
+
+impl<T: Bitmask + Clone + FromStr<Err = AocError>> FromStr for Machine<T> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let instructions = s
+            .lines()
+            .map(|line| line.parse().map_err(|error: AocError| error.within(s, line)))
+            .collect::<Result<Vec<Instruction<T>>, AocError>>()?;
+
+        Ok(Machine {
+            instructions,
+            pointer: 0,
+            memory: HashMap::new(),
+            // This mask will get immediately overwritten. It might be more
+            // correct to have this be an Option<Bitmask> to represent that
+            // there is no mask before we run the program, but that's a lot of
+            // hassle for something we don't need to check.
+            mask: T::from_str("")?
+        })
+    }
+}
+
+impl<T: Bitmask + Clone + FromStr<Err = AocError>> Machine<T> {
+    pub fn step(&mut self) {
+        if self.pointer >= self.instructions.len() {
+            return;
+        }
+
+        match &self.instructions[self.pointer] {
+            Instruction::Mask(bitmask) => self.mask = bitmask.clone(),
+            Instruction::Mem(addr, value) => {
+                for masked_addr in self.mask.apply_mem(*addr) {
+                    self.memory.insert(masked_addr, self.mask.apply_val(*value));
+                }
+            }
+        }
+
+        self.pointer += 1;
+    }
+
+    pub fn eval(&mut self) {
+        while self.pointer < self.instructions.len() {
+            self.step();
+        }
+    }
+}
+
+// The parts read the masks differently, so the program is parsed both ways.
+pub struct Program {
+    pub values: Machine<ValueBitmask>,
+    pub addresses: Machine<MemBitmask>
+}
+
+// Every floating bit doubles the number of addresses a write goes to. Real
+// inputs never have more than nine, a mask with many more than that would
+// have part two writing billions of addresses.
+const MAX_FLOATING: usize = 12;
+
+fn memory_sum(memory: &HashMap<u64, u64>) -> Result<u64, AocError> {
+    memory
+        .values()
+        .try_fold(0u64, |sum, value| sum.checked_add(*value))
+        .ok_or_else(|| AocError::Misc("Overflow".to_string()))
+}
+
+// The program as masks and writes, read straight from the text for the
+// references. Writes before the first mask see a mask that changes nothing.
+fn read_writes(input: &str) -> Result<Vec<(&str, u64, u64)>, AocError> {
+    let mut mask = "";
+    let mut writes = Vec::new();
+    for line in input.lines() {
+        if let Some(new_mask) = line.strip_prefix("mask = ") {
+            if new_mask.len() > 36 || new_mask.chars().any(|c| !"01X".contains(c)) {
+                return Err(AocError::parse("Bad mask", input, new_mask));
+            }
+            mask = new_mask;
+        } else {
+            let (address, value) = line
+                .strip_prefix("mem[")
+                .and_then(|line| line.split_once("] = "))
+                .ok_or_else(|| AocError::parse("Invalid instruction", input, line))?;
+            writes.push((mask, address.parse()?, value.parse()?));
+        }
+    }
+
+    Ok(writes)
+}
+
+// The mask's bits from the lowest up, as bit numbers.
+fn mask_bits(mask: &str) -> impl Iterator<Item = (u32, char)> + '_ {
+    mask.chars().rev().enumerate().map(|(bit, c)| (bit as u32, c))
+}
+
+fn reference_sum(memory: &HashMap<u64, u64>) -> Result<u64, AocError> {
+    memory
+        .values()
+        .try_fold(0u64, |sum, value| sum.checked_add(*value))
+        .ok_or_else(|| AocError::Misc("Overflow".to_string()))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Program;
+
+    fn parse(input: &str) -> Result<Program, AocError> {
+        Ok(Program {
+            values: input.parse()?,
+            addresses: input.parse()?
+        })
+    }
+
+    fn part_one(program: &Program) -> Result<Answer, AocError> {
+        let mut machine = program.values.clone();
+        machine.eval();
+
+        Ok(memory_sum(&machine.memory)?.into())
+    }
+
+    fn part_two(program: &Program) -> Result<Answer, AocError> {
+        let too_many_floating = program.addresses.instructions
+            .iter()
+            .any(|instruction| matches!(instruction, Instruction::Mask(mask) if mask.floats.len() > MAX_FLOATING));
+        if too_many_floating {
+            return Err(AocError::Misc("Too many floating bits".to_string()));
+        }
+
+        let mut machine = program.addresses.clone();
+        machine.eval();
+
+        Ok(memory_sum(&machine.memory)?.into())
+    }
+
+    // Size is how many lines, a mask followed by a few writes at a time. The
+    // masks have up to nine floating bits like the real ones.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut lines = Vec::with_capacity(size);
+        while lines.len() < size.max(2) {
+            let mut mask: Vec<char> = (0..36).map(|_| if rng.chance(0.5) { '1' } else { '0' }).collect();
+            for _ in 0..rng.between(1, 9) {
+                let bit = rng.below(mask.len());
+                mask[bit] = 'X';
+            }
+            lines.push(format!("mask = {}", mask.into_iter().collect::<String>()));
+
+            for _ in 0..rng.between(1, 6) {
+                lines.push(format!("mem[{}] = {}", rng.between(0, 65535), rng.between(0, 999_999_999)));
+            }
+        }
+        lines.truncate(size.max(2));
+
+        Some(lines.join("\n"))
+    }
+
+    // Sets and clears the value's bits one at a time.
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let mut memory = HashMap::new();
+        for (mask, address, mut value) in read_writes(input)? {
+            for (bit, c) in mask_bits(mask) {
+                match c {
+                    '0' => value &= !(1 << bit),
+                    '1' => value |= 1 << bit,
+                    _ => {}
+                }
+            }
+            memory.insert(address, value);
+        }
+
+        Ok(reference_sum(&memory)?.into())
+    }
+
+    // Doubles the list of addresses for every floating bit.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let mut memory = HashMap::new();
+        for (mask, address, value) in read_writes(input)? {
+            if mask.matches('X').count() > MAX_FLOATING {
+                return Err(AocError::Misc("Too many floating bits".to_string()));
+            }
+
+            let mut addresses = vec![address];
+            for (bit, c) in mask_bits(mask) {
+                match c {
+                    '1' => addresses.iter_mut().for_each(|address| *address |= 1 << bit),
+                    'X' => {
+                        addresses = addresses
+                            .iter()
+                            .flat_map(|address| [*address & !(1 << bit), *address | 1 << bit])
+                            .collect();
+                    },
+                    _ => {}
+                }
+            }
+            for address in addresses {
+                memory.insert(address, value);
+            }
+        }
+
+        Ok(reference_sum(&memory)?.into())
+    }
+}
+
+const EXAMPLE: &str = "
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+// Part two can't run the part one example, its masks have too many floating
+// bits to write every address.
+const FLOATING_EXAMPLE: &str = "
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "165"),
+    Example::new(Part::Two, FLOATING_EXAMPLE, "208")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_two;
+
+    #[test]
+    fn test_value_bitmask_parse() {
+        let s = "1101000X0110100X1X1X00001010XX00X0X0";
+        let bitmask: ValueBitmask = s.parse().unwrap();
+
+        let one_str = format!("{:b}", bitmask.ones);
+        let zero_str = format!("{:b}", bitmask.zeros);
+
+        assert_eq!(one_str, s.replace("X", "0"));
+        assert_eq!(zero_str, s.replace("X", "1"));
+    }
+
+    #[test]
+    fn test_value_bitmask_apply() {
+        let s = "XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X";
+        let bitmask: ValueBitmask = s.parse().unwrap();
+
+        assert_eq!(bitmask.apply_val(11), 73);
+    }
+
+    #[test]
+    fn test_mem_bitmask_parse() {
+        let s = "000000000000000000000000000000X1001X";
+        let bitmask: MemBitmask = s.parse().unwrap();
+
+        assert_eq!(bitmask.floats, vec![0, 5]);
+    }
+
+    #[test]
+    fn test_mem_bitmask_apply() {
+        let s = "000000000000000000000000000000X1001X";
+        let bitmask: MemBitmask = s.parse().unwrap();
+
+        let addresses: Vec<u64> = bitmask.apply_mem(42).collect();
+        assert_eq!(addresses, vec![ 26, 27, 58, 59 ]);
+    }
+
+    // Part two used to try writing every one of 2^36 addresses.
+    #[test]
+    fn too_many_floating_bits_is_an_error() {
+        assert!(solve_part_two::<Puzzle>("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX\nmem[0] = 1").is_err());
+    }
+}
\ No newline at end of file
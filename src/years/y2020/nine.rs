@@ -0,0 +1,242 @@
+use std::cmp::Ordering::*;
+use std::num::ParseIntError;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Encoding Error";
+
+// This is the dumb way to solve it, but on my machine and input its time is
+// indistinguishable from what should be the faster way using a hashset.
+// This code is easier to follow though, so I'm keeping it.
+fn first_invalid(input: &[usize], window: usize) -> Result<usize, AocError> {
+    for i in window..input.len() {
+        let candidate = &input[i];
+
+        let mut is_valid = false;
+        for x in &input[(i - window)..i] {
+            for y in &input[(i - window)..i] {
+                if x != y && x.checked_add(*y) == Some(*candidate) {
+                    is_valid = true;
+                    break;
+                }
+            }
+        }
+
+        if !is_valid {
+            return Ok(*candidate);
+        }
+    }
+
+    Err(AocError::Misc("No invalid number in input".to_string()))
+}
+
+fn subsequence_sum(input: &[usize], target: usize) -> Result<(usize, usize), AocError> {
+    let mut start = 0;
+    let mut running_sum = 0usize;
+
+    for (end, n) in input.iter().enumerate() {
+        running_sum = running_sum
+            .checked_add(*n)
+            .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+
+        while start <= end {
+            match running_sum.cmp(&target) {
+                // The puzzle wants at least two numbers, the target itself
+                // doesn't count.
+                Equal if start < end => return Ok((start, end)),
+                Equal | Less => break,
+                Greater => {
+                    running_sum -= input[start];
+                    start += 1;
+                }
+            }
+        }
+    }
+
+    Err(AocError::Misc("No subsequence adds to target".to_string()))
+}
+
+const PREAMBLE: usize = 25;
+
+// Both parts need the first invalid number, so it's found once along with
+// the parsing.
+pub struct Xmas {
+    pub numbers: Vec<usize>,
+    pub first_invalid: usize
+}
+
+impl Xmas {
+    pub fn new(input: &str, preamble: usize) -> Result<Xmas, AocError> {
+        let numbers = input
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<Vec<usize>, ParseIntError>>()?;
+        let first_invalid = first_invalid(&numbers, preamble)?;
+
+        Ok(Xmas { numbers, first_invalid })
+    }
+}
+
+fn is_pair_sum(window: &[u64], n: u64) -> bool {
+    window.iter().any(|x| window.iter().any(|y| x != y && x.checked_add(*y) == Some(n)))
+}
+
+fn reference_invalid(nums: &[u64]) -> Result<u64, AocError> {
+    (PREAMBLE..nums.len())
+        .map(|i| nums[i])
+        .zip(nums.windows(PREAMBLE))
+        .find(|(n, window)| !is_pair_sum(window, *n))
+        .map(|(n, _)| n)
+        .ok_or_else(|| AocError::Misc("No invalid number in input".to_string()))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Xmas;
+
+    fn parse(input: &str) -> Result<Xmas, AocError> {
+        Xmas::new(input, PREAMBLE)
+    }
+
+    fn part_one(xmas: &Xmas) -> Result<Answer, AocError> {
+        Ok(xmas.first_invalid.into())
+    }
+
+    fn part_two(xmas: &Xmas) -> Result<Answer, AocError> {
+        let nums = &xmas.numbers;
+        let (start, end) = subsequence_sum(nums, xmas.first_invalid)?;
+
+        // subsequence_sum never gives back an empty range.
+        let min = nums[start..=end].iter().min().unwrap();
+        let max = nums[start..=end].iter().max().unwrap();
+        let key = min
+            .checked_add(*max)
+            .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+
+        Ok(key.into())
+    }
+
+    // Size is how many numbers, but no more than 1000. Each one is a sum of
+    // two from the 25 before, so they grow exponentially however small the
+    // sums are kept, and not much past 1000 they'd stop fitting in a u64.
+    //
+    // The invalid number is the sum of a run of earlier numbers that isn't
+    // also a sum of two in its window. A run of everything before it always
+    // works, since that has to be bigger than any pair or any one number.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let count = size.clamp(PREAMBLE + 1, 1000);
+        let invalid_at = rng.between((count / 2).max(PREAMBLE) as u64, count as u64 - 1) as usize;
+
+        let mut preamble: Vec<u64> = (1..=60).collect();
+        rng.shuffle(&mut preamble);
+        let mut nums: Vec<u64> = preamble[..PREAMBLE].to_vec();
+
+        while nums.len() < count {
+            let window = &nums[nums.len() - PREAMBLE..];
+
+            if nums.len() == invalid_at {
+                let mut invalid = nums.iter().sum();
+                for _ in 0..100 {
+                    let start = rng.below(invalid_at - 1);
+                    let end = (start + rng.between(2, 5) as usize).min(invalid_at);
+                    let run = nums[start..end].iter().sum();
+                    if !is_pair_sum(window, run) {
+                        invalid = run;
+                        break;
+                    }
+                }
+                nums.push(invalid);
+                continue;
+            }
+
+            // Sums of the smallest few keep the growth as slow as it goes.
+            let mut smallest = window.to_vec();
+            smallest.sort_unstable();
+            smallest.dedup();
+            smallest.truncate(5);
+            let x = rng.below(smallest.len());
+            let y = (x + 1 + rng.below(smallest.len() - 1)) % smallest.len();
+            nums.push(smallest[x] + smallest[y]);
+        }
+
+        let lines: Vec<String> = nums.iter().map(u64::to_string).collect();
+        Some(lines.join("\n"))
+    }
+
+    // Every pair in every window.
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let nums = input
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<Vec<u64>, ParseIntError>>()?;
+
+        Ok(reference_invalid(&nums)?.into())
+    }
+
+    // Every run of two or more, adding each one up from scratch.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let nums = input
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<Vec<u64>, ParseIntError>>()?;
+        let invalid = reference_invalid(&nums)?;
+
+        for end in 1..nums.len() {
+            for start in 0..end {
+                let run = &nums[start..=end];
+                if run.iter().try_fold(0u64, |sum, n| sum.checked_add(*n)) == Some(invalid) {
+                    let key = run.iter().min().unwrap().checked_add(*run.iter().max().unwrap());
+                    return Ok(key.ok_or_else(|| AocError::Misc("Overflow".to_string()))?.into());
+                }
+            }
+        }
+
+        Err(AocError::Misc("No subsequence adds to target".to_string()))
+    }
+}
+
+const EXAMPLE: &str = "
+35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+// The example uses a preamble of 5 instead of 25.
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "127")
+        .with_solution(|input| Puzzle::part_one(&Xmas::new(input, 5)?)),
+    Example::new(Part::Two, EXAMPLE, "62")
+        .with_solution(|input| Puzzle::part_two(&Xmas::new(input, 5)?))
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The 5 on its own adds up to the target too, but part two used to take
+    // it rather than going on to the 1 and 4 after it.
+    #[test]
+    fn runs_are_two_numbers_or_more() {
+        assert_eq!(subsequence_sum(&[3, 5, 1, 4], 5).unwrap(), (2, 3));
+        assert!(subsequence_sum(&[3, 5], 5).is_err());
+    }
+}
\ No newline at end of file
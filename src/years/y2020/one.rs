@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::num::ParseIntError;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Report Repair";
+
+// How many pairs and triples of the numbers add up to 2020, counting a number
+// more than once too since that's an answer the parts could find by mistake.
+fn sums_to_2020(nums: &[u64]) -> (usize, usize) {
+    let mut pairs = 0;
+    let mut triples = 0;
+    for (i, a) in nums.iter().enumerate() {
+        for (j, b) in nums.iter().enumerate().skip(i) {
+            if a + b == 2020 {
+                pairs += 1;
+            }
+            triples += nums[j..].iter().filter(|c| a + b + *c == 2020).count();
+        }
+    }
+    (pairs, triples)
+}
+
+// The references only look at expenses that could be part of an answer, so
+// the sums can't overflow.
+fn read_expenses(input: &str) -> Result<Vec<u64>, AocError> {
+    let nums = input
+        .lines()
+        .map(|line| line.parse())
+        .collect::<Result<Vec<u64>, ParseIntError>>()?;
+
+    Ok(nums.into_iter().filter(|n| *n <= 2020).collect())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = HashSet<usize>;
+
+    fn parse(input: &str) -> Result<HashSet<usize>, AocError> {
+        let nums = input
+            .split('\n')
+            .map(|line| line.parse())
+            .collect::<Result<HashSet<usize>, ParseIntError>>()?;
+
+        Ok(nums)
+    }
+
+    fn part_one(nums: &HashSet<usize>) -> Result<Answer, AocError> {
+        let diffs: HashSet<usize> = nums
+            .iter()
+            .filter(|n| **n <= 2020)
+            .map(|n| 2020 - n)
+            .collect();
+
+        let answer = nums
+            .intersection(&diffs)
+            .try_fold(1usize, |product, n| product.checked_mul(*n))
+            .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+
+        Ok(answer.into())
+    }
+
+    fn part_two(nums: &HashSet<usize>) -> Result<Answer, AocError> {
+        for num in nums.iter().filter(|n| **n <= 2020) {
+            let target = 2020 - num;
+            let diffs: HashSet<usize> = nums
+                .iter()
+                .map(|n| if target > *n { target - n } else { 0 })
+                .collect();
+
+            let candidates: Vec<&usize> = nums
+                .intersection(&diffs)
+                .collect();
+
+            if candidates.len() == 2 {
+                let answer: usize = candidates
+                    .iter()
+                    // For reasons I don't fully understand, `product` is choking
+                    // on the type of iter here being &&usize, so I'll just do my
+                    // own fold.
+                    .fold(*num, |acc, x| acc * *x);
+
+                return Ok(answer.into());
+            }
+        }
+
+        Err(AocError::Misc("Didn't find a solution".to_string()))
+    }
+
+    // Size is how many expenses. Exactly one pair and one triple add up to
+    // 2020, everything else is over 1010 and kept clear of making another.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let planted = loop {
+            let a = rng.between(100, 1009);
+            let x = rng.between(100, 600);
+            let y = rng.between(100, 600);
+            let planted = vec![a, 2020 - a, x, y, 2020 - x - y];
+
+            let mut distinct = planted.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            if distinct.len() == planted.len() && sums_to_2020(&planted) == (1, 1) {
+                break planted;
+            }
+        };
+
+        // Anything over 1010 can only make 2020 along with one or two of the
+        // planted numbers, so those are ruled out along with the planted
+        // numbers themselves.
+        let mut taken: HashSet<u64> = planted.iter().copied().collect();
+        for (i, a) in planted.iter().enumerate() {
+            for b in &planted[i..] {
+                taken.insert(2020u64.saturating_sub(a + b));
+            }
+            taken.insert(2020 - a);
+        }
+
+        let mut expenses = planted.clone();
+        let highest = 1011 + 4 * size as u64;
+        while expenses.len() < size {
+            let n = rng.between(1011, highest);
+            if taken.insert(n) {
+                expenses.push(n);
+            }
+        }
+        rng.shuffle(&mut expenses);
+
+        let lines: Vec<String> = expenses.iter().map(u64::to_string).collect();
+        Some(lines.join("\n"))
+    }
+
+    // Every pair and every triple of lines in turn.
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let nums = read_expenses(input)?;
+        for (i, a) in nums.iter().enumerate() {
+            for b in &nums[i + 1..] {
+                if a + b == 2020 {
+                    return Ok((a * b).into());
+                }
+            }
+        }
+
+        Err(AocError::Misc("Didn't find a solution".to_string()))
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let nums = read_expenses(input)?;
+        for (i, a) in nums.iter().enumerate() {
+            for (j, b) in nums.iter().enumerate().skip(i + 1) {
+                for c in &nums[j + 1..] {
+                    if a + b + c == 2020 {
+                        return Ok((a * b * c).into());
+                    }
+                }
+            }
+        }
+
+        Err(AocError::Misc("Didn't find a solution".to_string()))
+    }
+}
+
+const EXAMPLE: &str = "
+1721
+979
+366
+299
+675
+1456";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "514579"),
+    Example::new(Part::Two, EXAMPLE, "241861950")
+];
\ No newline at end of file
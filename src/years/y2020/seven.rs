@@ -0,0 +1,330 @@
+use petgraph::{Directed, Direction};
+use petgraph::graphmap::GraphMap;
+use std::collections::{HashMap, HashSet};
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Handy Haversacks";
+
+#[derive(Debug)]
+pub struct BagSpec<'a> {
+    pub name: &'a str,
+    pub contents: Vec<(&'a str, u8)>
+}
+
+impl BagSpec<'_> {
+    // This can't be a FromStr impl because the result borrows from the input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(line: &str) -> Result<BagSpec<'_>, AocError> {
+        let (name, contents_str) = match line.split_once(" bags contain ") {
+            Some((name, contents_str)) => (name, contents_str),
+            None => return Err(AocError::parse("Invalid bag spec", line, line))
+        };
+
+        if contents_str == "no other bags." {
+            Ok(BagSpec{ name, contents: vec![] })
+        } else {
+            let contents = contents_str
+                .split(", ")
+                .map(|spec| {
+                    // Each one is "<count> <name> bag(s)".
+                    let (count, name) = spec
+                        .split_once(' ')
+                        .and_then(|(count, bag)| Some((count, bag.rsplit_once(" bag")?.0)))
+                        .ok_or_else(|| AocError::parse("Invalid bag spec", line, spec))?;
+                    let count = count
+                        .parse::<u8>()
+                        .map_err(|_| AocError::parse("Bad bag count", line, count))?;
+                    Ok((name, count))
+                })
+                .collect::<Result<Vec<(&str, u8)>, AocError>>()?;
+
+            Ok(BagSpec{ name, contents })
+        }
+    }
+}
+
+pub fn bag_graph(text: &str) -> Result<GraphMap<&str, u8, Directed>, AocError> {
+    let specs: Vec<BagSpec> = text
+        .lines()
+        .map(|line| BagSpec::from_str(line).map_err(|error| error.within(text, line)))
+        .collect::<Result<Vec<BagSpec>, AocError>>()?;
+
+    // This * 3 is a ballpark from eyeballing the input.
+    let mut graph = GraphMap::<&str, u8, Directed>::with_capacity(specs.len(), specs.len() * 3);
+
+    for spec in specs {
+        graph.add_node(spec.name);
+        for (name, weight) in spec.contents {
+            // By using a GraphMap we can safely add keys multiple times.
+            graph.add_node(name);
+            // Because GraphMap stores edges in both directions it doesn't
+            // really matter which direction we make the edge in, but this is
+            // conceptually simpler for part one.
+            graph.add_edge(name, spec.name, weight);
+        }
+    }
+
+    Ok(graph)
+}
+
+// Each bag's size is remembered once it's known, otherwise a bag that turns
+// up inside lots of others gets counted out again every time. A None is a bag
+// that's still being counted, reaching it again means it contains itself.
+fn count_bag_contents<'a>(
+    graph: &GraphMap<&'a str, u8, Directed>,
+    root: &'a str,
+    sizes: &mut HashMap<&'a str, Option<usize>>
+) -> Result<usize, AocError> {
+    match sizes.get(root) {
+        Some(Some(size)) => return Ok(*size),
+        Some(None) => return Err(AocError::Misc(format!("{} bags contain themselves", root))),
+        None => {}
+    }
+    sizes.insert(root, None);
+
+    let mut size = 0usize;
+    for node in graph.neighbors_directed(root, Direction::Incoming) {
+        let bag_count = *graph.edge_weight(node, root).unwrap() as usize;
+        size = count_bag_contents(graph, node, sizes)?
+            .checked_add(1)
+            .and_then(|inner| inner.checked_mul(bag_count))
+            .and_then(|inner| inner.checked_add(size))
+            .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+    }
+
+    sizes.insert(root, Some(size));
+    Ok(size)
+}
+
+const ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light",
+    "mirrored", "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy"
+];
+const COLORS: [&str; 33] = [
+    "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral",
+    "crimson", "cyan", "fuchsia", "gold", "gray", "green", "indigo", "lavender",
+    "lime", "magenta", "maroon", "olive", "orange", "plum", "purple", "red",
+    "salmon", "silver", "tan", "teal", "tomato", "turquoise", "violet", "white",
+    "yellow"
+];
+
+// The real inputs use every adjective with every color, past that the
+// adjectives get numbered to keep the names different.
+fn bag_name(i: usize) -> String {
+    let adjective = i / COLORS.len();
+    let color = COLORS[i % COLORS.len()];
+    match adjective / ADJECTIVES.len() {
+        0 => format!("{} {}", ADJECTIVES[adjective], color),
+        n => format!("{}{} {}", ADJECTIVES[adjective % ADJECTIVES.len()], n + 1, color)
+    }
+}
+
+// The references' own reading of the rules, each bag with what's directly
+// inside it.
+type ReferenceRules<'a> = HashMap<&'a str, Vec<(usize, &'a str)>>;
+
+fn read_rules(input: &str) -> Result<ReferenceRules<'_>, AocError> {
+    let mut rules = HashMap::new();
+    for line in input.lines() {
+        let bad_rule = || AocError::parse("Invalid bag spec", input, line);
+        let (outer, contents) = line.split_once(" bags contain ").ok_or_else(bad_rule)?;
+        let contents = contents.strip_suffix('.').ok_or_else(bad_rule)?;
+
+        let mut inside = Vec::new();
+        if contents != "no other bags" {
+            for spec in contents.split(", ") {
+                let spec = spec
+                    .strip_suffix(" bags")
+                    .or_else(|| spec.strip_suffix(" bag"))
+                    .ok_or_else(bad_rule)?;
+                let (count, name) = spec.split_once(' ').ok_or_else(bad_rule)?;
+                inside.push((count.parse()?, name));
+            }
+        }
+        rules.insert(outer, inside);
+    }
+
+    Ok(rules)
+}
+
+// Searches from scratch for every bag asked about. Depth stops a bag that
+// contains itself from recursing forever.
+fn contains_shiny_gold(rules: &ReferenceRules, bag: &str, depth: usize) -> Result<bool, AocError> {
+    if depth > rules.len() {
+        return Err(AocError::Misc(format!("{} bags contain themselves", bag)));
+    }
+
+    for (_, inner) in rules.get(bag).into_iter().flatten() {
+        if *inner == "shiny gold" || contains_shiny_gold(rules, inner, depth + 1)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Counts a bag's contents out again every time it turns up.
+fn bags_inside(rules: &ReferenceRules, bag: &str, depth: usize) -> Result<usize, AocError> {
+    if depth > rules.len() {
+        return Err(AocError::Misc(format!("{} bags contain themselves", bag)));
+    }
+
+    let mut total = 0usize;
+    for (count, inner) in rules.get(bag).into_iter().flatten() {
+        total = bags_inside(rules, inner, depth + 1)?
+            .checked_add(1)
+            .and_then(|inner| inner.checked_mul(*count))
+            .and_then(|inner| inner.checked_add(total))
+            .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+    }
+    Ok(total)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = GraphMap<&'a str, u8, Directed>;
+
+    fn parse(input: &str) -> Result<GraphMap<&str, u8, Directed>, AocError> {
+        bag_graph(input)
+    }
+
+    fn part_one(graph: &GraphMap<&str, u8, Directed>) -> Result<Answer, AocError> {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut to_visit: HashSet<&str> = HashSet::new();
+        to_visit.insert("shiny gold");
+
+        loop {
+            if to_visit.is_empty() { break; }
+
+            let popped = to_visit.iter().next().cloned().unwrap();
+            visited.insert(popped);
+            to_visit.remove(popped);
+
+            for node in graph.neighbors_directed(popped, Direction::Outgoing) {
+                if !visited.contains(node) {
+                    to_visit.insert(node);
+                }
+            }
+        }
+
+        // Visited includes the bag we started at, sub that out.
+        let parent_bag_count = visited.len() - 1;
+
+        Ok(parent_bag_count.into())
+    }
+
+    fn part_two(graph: &GraphMap<&str, u8, Directed>) -> Result<Answer, AocError> {
+        let bag_size = count_bag_contents(graph, "shiny gold", &mut HashMap::new())?;
+
+        Ok(bag_size.into())
+    }
+
+    // Size is how many rules. Every bag is given a level and only holds bags
+    // from the levels below it, so there are no cycles, and with only a few
+    // levels part two's count stays well inside a usize.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const LEVELS: usize = 8;
+
+        let shiny_gold = ADJECTIVES.iter().position(|a| *a == "shiny").unwrap() * COLORS.len() +
+            COLORS.iter().position(|c| *c == "gold").unwrap();
+        let mut names: Vec<String> = (0..size.max(1)).map(bag_name).collect();
+        if shiny_gold >= names.len() {
+            let i = rng.below(names.len());
+            names[i] = bag_name(shiny_gold);
+        }
+
+        let mut levels: Vec<Vec<&str>> = vec![Vec::new(); LEVELS];
+        for name in &names {
+            let level = if name == "shiny gold" { LEVELS / 2 } else { rng.below(LEVELS) };
+            levels[level].push(name);
+        }
+
+        let mut rules = Vec::new();
+        for level in 0..LEVELS {
+            for name in &levels[level] {
+                let mut contents: Vec<String> = Vec::new();
+                let mut inside: Vec<&str> = Vec::new();
+                for _ in 0..rng.between(0, 4) {
+                    let below = &levels[rng.below(level.max(1))];
+                    if level == 0 || below.is_empty() {
+                        continue;
+                    }
+                    let bag = *rng.choose(below);
+                    if inside.contains(&bag) {
+                        continue;
+                    }
+                    inside.push(bag);
+
+                    let count = rng.between(1, 5);
+                    contents.push(format!("{} {} bag{}", count, bag, if count == 1 { "" } else { "s" }));
+                }
+
+                if contents.is_empty() {
+                    rules.push(format!("{} bags contain no other bags.", name));
+                } else {
+                    rules.push(format!("{} bags contain {}.", name, contents.join(", ")));
+                }
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        Some(rules.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let rules = read_rules(input)?;
+        let mut count = 0;
+        for bag in rules.keys() {
+            if contains_shiny_gold(&rules, bag, 0)? {
+                count += 1;
+            }
+        }
+
+        Ok(count.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        Ok(bags_inside(&read_rules(input)?, "shiny gold", 0)?.into())
+    }
+}
+
+const EXAMPLE: &str = "
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+const DEEP_EXAMPLE: &str = "
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "4"),
+    Example::new(Part::Two, EXAMPLE, "32"),
+    Example::new(Part::Two, DEEP_EXAMPLE, "126")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_two;
+
+    // A bag inside itself used to recurse until the stack ran out.
+    #[test]
+    fn bags_inside_themselves_are_errors() {
+        assert!(solve_part_two::<Puzzle>("shiny gold bags contain 1 shiny gold bag.").is_err());
+    }
+}
\ No newline at end of file
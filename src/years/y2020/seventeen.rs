@@ -1,18 +1,21 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
 
 pub const NAME: &str = "Conway Cubes";
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Vec4 {
-    x: isize,
-    y: isize,
-    z: isize,
-    a: isize
+pub struct Vec4 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+    pub a: isize
 }
 
 impl Vec4 {
-    fn neighbors(&self) -> Neighbors {
+    pub fn neighbors(&self) -> Neighbors {
         Neighbors {
             point: *self,
             dx: -1,
@@ -23,7 +26,7 @@ impl Vec4 {
     }
 }
 
-struct Neighbors {
+pub struct Neighbors {
     point: Vec4,
     dx: isize,
     dy: isize,
@@ -71,13 +74,13 @@ impl Iterator for Neighbors {
     }
 }
 
-#[derive(Debug)]
-struct Conway {
-    active: HashSet<Vec4>
+#[derive(Debug, Clone)]
+pub struct Conway {
+    pub active: HashSet<Vec4>
 }
 
 impl Conway {
-    fn new(initial: &str) -> Conway {
+    pub fn new(initial: &str) -> Conway {
         let active = initial
             .lines()
             .enumerate()
@@ -97,14 +100,14 @@ impl Conway {
         Conway { active }
     }
 
-    fn active_adjacent(&self, point: Vec4) -> usize {
+    pub fn active_adjacent(&self, point: Vec4) -> usize {
         point
             .neighbors()
             .filter(|p| self.active.contains(p))
             .count()
     }
 
-    fn run(&mut self, steps: usize) {
+    pub fn run(&mut self, steps: usize) {
         if self.active.is_empty() {
             return;
         }
@@ -139,7 +142,7 @@ impl Conway {
                             let neighbor_count = self.active_adjacent(point);
 
                             if self.active.contains(&point) &&
-                               (neighbor_count < 2 || neighbor_count > 3) {
+                               !(2..=3).contains(&neighbor_count) {
                                 to_remove.push(point);
                             }
 
@@ -163,13 +166,92 @@ impl Conway {
     }
 }
 
-pub fn part_one(_input: &str) -> Result<String, AocError> {
-    Ok("Not implemented".to_string())
+// The cubes in however many dimensions, counting each cell's active
+// neighbors by going round every active cube and adding one to everything
+// next to it.
+fn reference_boot(input: &str, dimensions: usize) -> Result<usize, AocError> {
+    let mut active: HashSet<Vec<isize>> = HashSet::new();
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if c == '#' {
+                let mut cube = vec![0; dimensions];
+                cube[0] = x as isize;
+                cube[1] = y as isize;
+                active.insert(cube);
+            }
+        }
+    }
+
+    // Every combination of -1, 0 and 1 apart from staying put.
+    let offsets: Vec<Vec<isize>> = (0..3usize.pow(dimensions as u32))
+        .map(|i| (0..dimensions).map(|d| (i / 3usize.pow(d as u32) % 3) as isize - 1).collect())
+        .filter(|offset: &Vec<isize>| offset.iter().any(|d| *d != 0))
+        .collect();
+
+    for _ in 0..6 {
+        let mut neighbors: HashMap<Vec<isize>, usize> = HashMap::new();
+        for cube in &active {
+            for offset in &offsets {
+                let next_to: Vec<isize> = cube.iter().zip(offset).map(|(a, b)| a + b).collect();
+                *neighbors.entry(next_to).or_insert(0) += 1;
+            }
+        }
+
+        active = neighbors
+            .into_iter()
+            .filter(|(cube, count)| *count == 3 || (*count == 2 && active.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+
+    Ok(active.len())
 }
 
-pub fn part_two(input: &str) -> Result<String, AocError> {
-    let mut conway = Conway::new(input);
-    conway.run(6);
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Conway;
+
+    fn parse(input: &str) -> Result<Conway, AocError> {
+        Ok(Conway::new(input))
+    }
+
+    fn part_one(_conway: &Conway) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_two(conway: &Conway) -> Result<Answer, AocError> {
+        let mut conway = conway.clone();
+        conway.run(6);
+
+        Ok(conway.active.len().into())
+    }
+
+    // Size is the width and height of the starting slice, the real one is 8.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let side = size.max(1);
+        let rows: Vec<String> = (0..side)
+            .map(|_| (0..side).map(|_| if rng.chance(0.5) { '#' } else { '.' }).collect())
+            .collect();
+
+        Some(rows.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_boot(input, 3)?.into())
+    }
 
-    Ok(conway.active.len().to_string())
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_boot(input, 4)?.into())
+    }
 }
+
+const EXAMPLE: &str = "
+.#.
+..#
+###";
+
+// Part one isn't implemented yet, when it is its answer for this is 112.
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::Two, EXAMPLE, "848")
+];
\ No newline at end of file
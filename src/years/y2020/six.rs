@@ -0,0 +1,148 @@
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Custom Customs";
+
+fn letter2bit(c: &char) -> u32 {
+    1 << (*c as u8 - 97)
+}
+
+pub fn answer_bits(s: &str) -> u32 {
+    let mut answers = 0u32;
+    for c in s.chars() {
+        answers |= letter2bit(&c);
+    }
+    answers
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    // Each group's answers, one bitset per person.
+    type Parsed<'a> = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<u32>>, AocError> {
+        if let Some(i) = input.find(|c: char| !c.is_ascii_lowercase() && c != '\n') {
+            return Err(AocError::parse("Answers are the letters a to z", input, &input[i..]));
+        }
+
+        let groups = input
+            .split("\n\n")
+            .map(|group| group.lines().map(answer_bits).collect())
+            .collect();
+
+        Ok(groups)
+    }
+
+    fn part_one(groups: &Vec<Vec<u32>>) -> Result<Answer, AocError> {
+        let answer_count_sum: u32 = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .fold(0, |acc, x| acc | x)
+                    .count_ones()
+            })
+            .sum();
+
+        Ok(answer_count_sum.into())
+    }
+
+    fn part_two(groups: &Vec<Vec<u32>>) -> Result<Answer, AocError> {
+        let answer_count_sum: u32 = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .fold(0xFFFFFFFF, |acc, x| acc & x)
+                    .count_ones()
+            })
+            .sum();
+
+        Ok(answer_count_sum.into())
+    }
+
+    // Size is how many groups, of one to five people. Everyone in a group
+    // shares a few answers so that part two has something to count.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let letters: Vec<char> = ('a'..='z').collect();
+
+        let groups: Vec<String> = (0..size)
+            .map(|_| {
+                let shared: Vec<char> = letters.iter().copied().filter(|_| rng.chance(0.15)).collect();
+                let people: Vec<String> = (0..rng.between(1, 5))
+                    .map(|_| {
+                        let mut answers: Vec<char> = letters
+                            .iter()
+                            .copied()
+                            .filter(|c| shared.contains(c) || rng.chance(0.2))
+                            .collect();
+                        if answers.is_empty() {
+                            answers.push(*rng.choose(&letters));
+                        }
+                        rng.shuffle(&mut answers);
+                        answers.into_iter().collect()
+                    })
+                    .collect();
+                people.join("\n")
+            })
+            .collect();
+
+        Some(groups.join("\n\n"))
+    }
+
+    // Each letter of the alphabet in turn, checked against every person.
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let mut count = 0;
+        for group in input.split("\n\n") {
+            count += ('a'..='z').filter(|c| group.lines().any(|person| person.contains(*c))).count();
+        }
+
+        Ok(count.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let mut count = 0;
+        for group in input.split("\n\n") {
+            count += ('a'..='z').filter(|c| group.lines().all(|person| person.contains(*c))).count();
+        }
+
+        Ok(count.into())
+    }
+}
+
+const EXAMPLE: &str = "
+abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "11"),
+    Example::new(Part::Two, EXAMPLE, "6")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // Capital letters used to index outside the 26 answers.
+    #[test]
+    fn answers_are_lowercase_letters() {
+        assert!(solve_part_one::<Puzzle>("Abc").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,588 @@
+use std::str::FromStr;
+use std::ops::RangeInclusive;
+use std::collections::{HashMap, HashSet};
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Ticket Translation";
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub rules: Vec<RangeInclusive<usize>>
+}
+
+impl FromStr for Field {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((name, rules_str)) = s.split_once(": ") {
+            let rules = rules_str
+                .split(" or ")
+                .map(|range| {
+                    if let Some((low, high)) = range.split_once("-") {
+                        let low = low.parse().map_err(|_| AocError::parse("Bad number", s, low))?;
+                        let high = high.parse().map_err(|_| AocError::parse("Bad number", s, high))?;
+                        Ok(low..=high)
+                    } else {
+                        Err(AocError::parse("Invalid range string", s, range))
+                    }
+                })
+                .collect::<Result<Vec<RangeInclusive<usize>>, AocError>>()?;
+
+            Ok(Field {
+                name: name.to_string(),
+                rules
+            })
+        } else {
+            Err(AocError::parse("Invalid field string", s, s))
+        }
+    }
+}
+
+impl Field {
+    pub fn valid(&self, x: usize) -> bool {
+        self.rules.iter().any(|range| range.contains(&x))
+    }
+}
+
+#[derive(Debug)]
+pub struct Fields(pub Vec<Field>);
+
+impl FromStr for Fields {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s
+            .lines()
+            .map(|line| line.parse().map_err(|error: AocError| error.within(s, line)))
+            .collect::<Result<Vec<Field>, AocError>>()?;
+        Ok(Fields(fields))
+    }
+}
+
+impl Fields {
+    pub fn valid_any(&self, x: usize) -> bool {
+        self.0
+            .iter()
+            .any(|field| field.valid(x))
+    }
+}
+
+#[derive(Debug)]
+pub struct Ticket(pub Vec<usize>);
+
+impl FromStr for Ticket {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nums = s
+            .split(',')
+            .map(|n| n.parse().map_err(|_| AocError::parse("Bad number", s, n)))
+            .collect::<Result<Vec<usize>, AocError>>()?;
+
+        Ok(Ticket(nums))
+    }
+}
+
+impl Ticket {
+    // None if the sum overflows.
+    pub fn invalid_sum(&self, fields: &Fields) -> Option<usize> {
+        self.0
+            .iter()
+            .filter(|x| !fields.valid_any(**x))
+            .try_fold(0usize, |sum, x| sum.checked_add(*x))
+    }
+
+    // One of the invalid numbers is a zero, so just checking invalid_sum == 0
+    // won't catch all the invalid fields.
+    pub fn invalid_count(&self, fields: &Fields) -> usize {
+        self.0
+            .iter()
+            .filter(|x| !fields.valid_any(**x))
+            .count()
+    }
+}
+
+pub struct Notes {
+    pub fields: Fields,
+    pub mine: Ticket,
+    pub nearby: Vec<Ticket>
+}
+
+fn overflow() -> AocError {
+    AocError::Misc("Overflow".to_string())
+}
+
+// Part two reads the tickets a column per field, so each one needs a number
+// for every field.
+fn check_length(ticket: &Ticket, fields: &Fields, input: &str, line: &str) -> Result<(), AocError> {
+    if ticket.0.len() != fields.0.len() {
+        let message = format!("Ticket has {} numbers for {} fields", ticket.0.len(), fields.0.len());
+        return Err(AocError::parse(&message, input, line));
+    }
+    Ok(())
+}
+
+const FIELD_NAMES: [&str; 20] = [
+    "departure location", "departure station", "departure platform", "departure track",
+    "departure date", "departure time", "arrival location", "arrival station",
+    "arrival platform", "arrival track", "class", "duration", "price", "route", "row",
+    "seat", "train", "type", "wagon", "zone"
+];
+
+// The references' reading of the notes: each field's name and ranges, my
+// ticket, and the nearby tickets.
+type ReferenceNotes<'a> = (Vec<(&'a str, Vec<(usize, usize)>)>, Vec<usize>, Vec<Vec<usize>>);
+
+fn read_notes(input: &str) -> Result<ReferenceNotes<'_>, AocError> {
+    let sections: Vec<&str> = input.split("\n\n").collect();
+    if sections.len() != 3 {
+        return Err(AocError::Misc("Wrong number of input parts".to_string()));
+    }
+
+    let mut fields = Vec::new();
+    for line in sections[0].lines() {
+        let (name, ranges) = line
+            .split_once(": ")
+            .ok_or_else(|| AocError::parse("Invalid field string", input, line))?;
+        let ranges = ranges
+            .split(" or ")
+            .map(|range| match range.split_once('-') {
+                Some((low, high)) => Ok((low.parse()?, high.parse()?)),
+                None => Err(AocError::parse("Invalid range string", input, range))
+            })
+            .collect::<Result<Vec<(usize, usize)>, AocError>>()?;
+        fields.push((name, ranges));
+    }
+
+    let ticket = |line: &str| {
+        let numbers = line
+            .split(',')
+            .map(|n| n.parse().map_err(AocError::from))
+            .collect::<Result<Vec<usize>, AocError>>()?;
+        if numbers.len() != fields.len() {
+            return Err(AocError::parse("Ticket has the wrong number of numbers", input, line));
+        }
+        Ok(numbers)
+    };
+    let mine = ticket(sections[1].lines().nth(1).unwrap_or(""))?;
+    let nearby = sections[2].lines().skip(1).map(ticket).collect::<Result<Vec<Vec<usize>>, AocError>>()?;
+
+    Ok((fields, mine, nearby))
+}
+
+fn allows(ranges: &[(usize, usize)], n: usize) -> bool {
+    ranges.iter().any(|(low, high)| *low <= n && n <= *high)
+}
+
+// How many ways there are to give the columns from column on a field each,
+// out of the fields not in used, stopping counting at two. Remembering the
+// counts for each set of used fields keeps this from trying every order.
+fn count_assignments(fits: &[Vec<bool>], column: usize, used: u64, counts: &mut HashMap<u64, usize>) -> usize {
+    if column == fits.len() {
+        return 1;
+    }
+    if let Some(count) = counts.get(&used) {
+        return *count;
+    }
+
+    let mut count = 0;
+    for field in 0..fits.len() {
+        if count < 2 && used & (1 << field) == 0 && fits[column][field] {
+            count += count_assignments(fits, column + 1, used | 1 << field, counts);
+        }
+    }
+    let count = count.min(2);
+    counts.insert(used, count);
+    count
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Notes;
+
+    fn parse(input: &str) -> Result<Notes, AocError> {
+        let parts: Vec<&str> = input.split("\n\n").collect();
+
+        if parts.len() != 3 {
+            return Err(AocError::Misc("Wrong number of input parts".to_string()))
+        }
+
+        let fields: Fields = parts[0]
+            .parse()
+            .map_err(|error: AocError| error.within(input, parts[0]))?;
+
+        let mine_line = parts[1]
+            .lines()
+            .nth(1)
+            .ok_or_else(|| AocError::parse("Missing your ticket", input, parts[1]))?;
+        let mine: Ticket = mine_line
+            .parse()
+            .map_err(|error: AocError| error.within(input, mine_line))?;
+        check_length(&mine, &fields, input, mine_line)?;
+
+        let nearby = parts[2]
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let ticket = line.parse().map_err(|error: AocError| error.within(input, line))?;
+                check_length(&ticket, &fields, input, line)?;
+                Ok(ticket)
+            })
+            .collect::<Result<Vec<Ticket>, AocError>>()?;
+
+        Ok(Notes { fields, mine, nearby })
+    }
+
+    fn part_one(notes: &Notes) -> Result<Answer, AocError> {
+        let answer = notes.nearby
+            .iter()
+            .try_fold(0usize, |sum, ticket| sum.checked_add(ticket.invalid_sum(&notes.fields)?))
+            .ok_or_else(overflow)?;
+
+        Ok(answer.into())
+    }
+
+    fn part_two(notes: &Notes) -> Result<Answer, AocError> {
+        let Notes { fields, mine: my_ticket, nearby: nearby_tickets } = notes;
+
+        let valid_tickets: Vec<&Ticket> = nearby_tickets
+            .iter()
+            .filter(|ticket| ticket.invalid_count(fields) == 0)
+            .collect();
+
+        let mut candidate_assignments: Vec<(usize, HashSet<&Field>)> = Vec::new();
+
+        for i in 0..fields.0.len() {
+            let column_numbers: Vec<usize> = valid_tickets
+                .iter()
+                .map(|ticket| ticket.0[i])
+                .collect();
+
+            let mut candidates = HashSet::new();
+            for field in &fields.0 {
+                let candidate = column_numbers
+                    .iter()
+                    .all(|x| field.valid(*x));
+
+                if candidate {
+                    candidates.insert(field);
+                }
+            }
+
+            candidate_assignments.push((i, candidates));
+        }
+
+        candidate_assignments.sort_unstable_by_key(|(_, cs)| cs.len());
+
+        let mut assignments: Vec<(usize, &Field)> = Vec::new();
+        for (i, candidates) in candidate_assignments.iter_mut() {
+            for assignment in &assignments {
+                candidates.remove(assignment.1);
+            }
+
+            if candidates.len() != 1 {
+                return Err(AocError::Misc("Can't tell which field a column is".to_string()));
+            }
+
+            let field = candidates.iter().next().unwrap();
+            assignments.push((*i, field));
+        }
+
+        let product = assignments
+            .iter()
+            .filter(|(_, field)| field.name.starts_with("departure"))
+            .try_fold(1usize, |product, (i, _)| product.checked_mul(my_ticket.0[*i]))
+            .ok_or_else(overflow)?;
+
+        Ok(product.into())
+    }
+
+    // The notes have to be in three sections, and part two reads each column
+    // of the tickets as a field so every ticket needs a number per field.
+    fn validate(input: &str) -> Option<Vec<AocError>> {
+        let sections: Vec<&str> = input.split("\n\n").collect();
+        if sections.len() != 3 {
+            let problem = match sections.get(3) {
+                Some(extra) => AocError::parse("More than three sections", input, extra),
+                None => AocError::Misc(format!("Expected three sections, found {}", sections.len()))
+            };
+            return Some(vec![problem]);
+        }
+
+        let mut problems = Vec::new();
+        let field_count = sections[0].lines().count();
+        let headers = [(sections[1], "your ticket:"), (sections[2], "nearby tickets:")];
+
+        for (section, header) in IntoIterator::into_iter(headers) {
+            let mut lines = section.lines();
+            match lines.next() {
+                Some(line) if line == header => {},
+                Some(line) => problems.push(AocError::parse(&format!("Expected \"{}\"", header), input, line)),
+                None => {}
+            }
+
+            for ticket in lines {
+                let count = ticket.split(',').count();
+                if count != field_count {
+                    let message = format!("Ticket has {} numbers for {} fields", count, field_count);
+                    problems.push(AocError::parse(&message, input, ticket));
+                }
+            }
+        }
+
+        Some(problems)
+    }
+
+    // Size is how many nearby tickets, about a quarter of them with a number
+    // no field allows. There are 20 fields like the real notes, each one's
+    // ranges inside the one before's, so that a column fits its own field
+    // and every field before it. That way only one column fits a single
+    // field, only one more fits two, and so on, which is what part two's
+    // elimination relies on.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let count = FIELD_NAMES.len();
+        let mut names = FIELD_NAMES.to_vec();
+        rng.shuffle(&mut names);
+
+        // Field i allows starts[i] to ends[i], apart from a hole of holes[i]
+        // either side of 500.
+        let starts: Vec<u64> = (0..count as u64).map(|i| 30 + 10 * i + rng.between(0, 9)).collect();
+        let ends: Vec<u64> = (0..count as u64).map(|i| 970 - 10 * i - rng.between(0, 9)).collect();
+        let holes: Vec<u64> = (0..count as u64).map(|i| 10 + 10 * i + rng.between(0, 9)).collect();
+        let allows = |i: usize, n: u64| {
+            (starts[i]..=ends[i]).contains(&n) && !(500 - holes[i]..=500 + holes[i]).contains(&n)
+        };
+        // A number field i allows, and if last_only then one that the field
+        // after it doesn't.
+        let number = |rng: &mut Rng, i: usize, last_only: bool| loop {
+            let n = rng.between(starts[i], ends[i]);
+            if allows(i, n) && !(last_only && i + 1 < count && allows(i + 1, n)) {
+                return n;
+            }
+        };
+
+        let mut columns: Vec<usize> = (0..count).collect();
+        rng.shuffle(&mut columns);
+        let ticket = |rng: &mut Rng| -> Vec<u64> {
+            columns.iter().map(|field| number(rng, *field, false)).collect()
+        };
+
+        let mine = ticket(rng);
+        let mut nearby: Vec<Vec<u64>> = (0..size.max(1)).map(|_| ticket(rng)).collect();
+
+        // Everything allowed by any field is allowed by the first, these are
+        // all outside it. The first ticket is left alone so that there's
+        // always a valid one.
+        let mut valid = vec![0];
+        for (i, ticket) in nearby.iter_mut().enumerate().skip(1) {
+            if rng.chance(0.25) {
+                let column = rng.below(count);
+                ticket[column] = match rng.below(3) {
+                    0 => rng.between(0, starts[0] - 1),
+                    1 => rng.between(ends[0] + 1, 999),
+                    _ => rng.between(500 - holes[0], 500 + holes[0])
+                };
+            } else {
+                valid.push(i);
+            }
+        }
+
+        // Every column needs a number that rules out the next field along,
+        // on a ticket that part two doesn't throw away.
+        for (column, field) in columns.iter().enumerate() {
+            let i = *rng.choose(&valid);
+            nearby[i][column] = number(rng, *field, true);
+        }
+
+        let mut fields: Vec<String> = (0..count)
+            .map(|i| format!(
+                "{}: {}-{} or {}-{}",
+                names[i], starts[i], 500 - holes[i] - 1, 500 + holes[i] + 1, ends[i]
+            ))
+            .collect();
+        rng.shuffle(&mut fields);
+
+        let join = |ticket: &Vec<u64>| ticket.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+        let nearby: Vec<String> = nearby.iter().map(join).collect();
+
+        Some(format!(
+            "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+            fields.join("\n"),
+            join(&mine),
+            nearby.join("\n")
+        ))
+    }
+
+    // Rules that still only fit the columns together one way, but not in an
+    // order part two's elimination can follow. The fields come in groups of
+    // four, each group covering its own stretch of numbers split into four
+    // windows, a column's numbers all coming from one window:
+    //
+    //     window:    1     2     3     4
+    //     first:     *
+    //     second:    *     *
+    //     third:           *     *
+    //     fourth:          *     *     *
+    //
+    // Window 4 only fits the fourth field and window 3 then only the third,
+    // leaving the second for window 2 and the first for window 1. Part two
+    // takes the columns with the fewest fields first though, and gets to
+    // window 1's pair before window 2's three have been narrowed down.
+    fn generate_tricky(rng: &mut Rng, size: usize) -> Option<String> {
+        let count = FIELD_NAMES.len();
+        let mut names = FIELD_NAMES.to_vec();
+        rng.shuffle(&mut names);
+
+        // Group g's windows start at 50 + 190 * g, 40 numbers each, leaving
+        // a gap of 30 before the next group that no field allows.
+        let window = |field: usize| 50 + 190 * (field / 4) as u64 + 40 * (field % 4) as u64;
+        let spans = [(0, 0), (0, 1), (1, 2), (1, 3)];
+        let mut fields: Vec<String> = (0..count)
+            .map(|field| {
+                let (first, last) = spans[field % 4];
+                let (low, high) = (window(field - field % 4 + first), window(field - field % 4 + last) + 39);
+                let split = rng.between(low, high - 1);
+                format!("{}: {}-{} or {}-{}", names[field], low, split, split + 1, high)
+            })
+            .collect();
+        rng.shuffle(&mut fields);
+
+        let mut columns: Vec<usize> = (0..count).collect();
+        rng.shuffle(&mut columns);
+        let ticket = |rng: &mut Rng| -> Vec<u64> {
+            columns.iter().map(|field| window(*field) + rng.between(0, 39)).collect()
+        };
+
+        let mine = ticket(rng);
+        let mut nearby: Vec<Vec<u64>> = (0..size.max(1)).map(|_| ticket(rng)).collect();
+        for ticket in nearby.iter_mut().skip(1) {
+            if rng.chance(0.25) {
+                let group = rng.below(count / 4) as u64;
+                ticket[rng.below(count)] = match rng.below(3) {
+                    0 => rng.between(0, 49),
+                    1 => rng.between(970, 999),
+                    _ => 50 + 190 * group + rng.between(160, 189)
+                };
+            }
+        }
+
+        let join = |ticket: &Vec<u64>| ticket.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+        let nearby: Vec<String> = nearby.iter().map(join).collect();
+
+        Some(format!(
+            "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+            fields.join("\n"),
+            join(&mine),
+            nearby.join("\n")
+        ))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let (fields, _, nearby) = read_notes(input)?;
+        let mut sum = 0usize;
+        for n in nearby.iter().flatten() {
+            if !fields.iter().any(|(_, ranges)| allows(ranges, *n)) {
+                sum = sum.checked_add(*n).ok_or_else(overflow)?;
+            }
+        }
+
+        Ok(sum.into())
+    }
+
+    // Rather than whittling down the candidates, this counts every way of
+    // matching columns to fields and only answers if there's exactly one.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let (fields, mine, nearby) = read_notes(input)?;
+        if fields.len() > 64 {
+            return Err(AocError::Misc("Too many fields".to_string()));
+        }
+
+        let valid: Vec<&Vec<usize>> = nearby
+            .iter()
+            .filter(|ticket| ticket.iter().all(|n| fields.iter().any(|(_, ranges)| allows(ranges, *n))))
+            .collect();
+        let fits: Vec<Vec<bool>> = (0..fields.len())
+            .map(|column| {
+                fields
+                    .iter()
+                    .map(|(_, ranges)| valid.iter().all(|ticket| allows(ranges, ticket[column])))
+                    .collect()
+            })
+            .collect();
+
+        let mut counts = HashMap::new();
+        if count_assignments(&fits, 0, 0, &mut counts) != 1 {
+            return Err(AocError::Misc("Can't tell which field a column is".to_string()));
+        }
+
+        // With only one way to do it, each column takes the first field that
+        // still leaves a way to do the rest.
+        let mut used = 0u64;
+        let mut product = 1usize;
+        for (column, n) in mine.iter().enumerate() {
+            let field = (0..fields.len())
+                .find(|field| {
+                    used & (1 << field) == 0 &&
+                        fits[column][*field] &&
+                        count_assignments(&fits, column + 1, used | 1 << field, &mut counts) == 1
+                })
+                .unwrap();
+            used |= 1 << field;
+            if fields[field].0.starts_with("departure") {
+                product = product.checked_mul(*n).ok_or_else(overflow)?;
+            }
+        }
+
+        Ok(product.into())
+    }
+}
+
+const EXAMPLE: &str = "
+class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+// The puzzle's part two example has no departure fields, so two of them have
+// been renamed here to give an answer that isn't just an empty product.
+const DEPARTURE_EXAMPLE: &str = "
+departure class: 0-1 or 4-19
+departure row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "71"),
+    Example::new(Part::Two, DEPARTURE_EXAMPLE, "132")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // A ticket short of a number used to index past its end.
+    #[test]
+    fn tickets_need_a_number_per_field() {
+        assert!(solve_part_one::<Puzzle>("a: 1-2\n\nyour ticket:\n1,2\n\nnearby tickets:\n1").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,339 @@
+use std::num::ParseIntError;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Adapter Array";
+
+// The outlet, the adapters in order, and the device, for the references.
+fn reference_chain(input: &str) -> Result<Vec<u64>, AocError> {
+    let mut chain = vec![0u64];
+    for line in input.lines() {
+        chain.push(line.parse()?);
+    }
+    chain.sort_unstable();
+
+    let device = chain[chain.len() - 1]
+        .checked_add(3)
+        .ok_or_else(|| AocError::Misc("Adapter too big".to_string()))?;
+    chain.push(device);
+
+    Ok(chain)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    // The adapters in order, with the outlet and the device on either end.
+    type Parsed<'a> = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Vec<u64>, AocError> {
+        let mut nums: Vec<u64> = input
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<Vec<u64>, ParseIntError>>()?;
+
+        nums.sort_unstable();
+        nums.insert(0, 0);
+        let device = nums[nums.len() - 1]
+            .checked_add(3)
+            .ok_or_else(|| AocError::Misc("Adapter too big".to_string()))?;
+        nums.push(device);
+
+        Ok(nums)
+    }
+
+    fn part_one(nums: &Vec<u64>) -> Result<Answer, AocError> {
+        let (ones, threes) = nums
+            .iter()
+            .zip(nums[1..].iter())
+            .map(|(adapter, next)| next - adapter)
+            .fold((0usize, 0usize), |(ones, threes), x| {
+                match x {
+                    1 => (ones + 1, threes),
+                    3 => (ones, threes + 1),
+                    _ => (ones, threes)
+                }
+            });
+
+        let product = ones
+            .checked_mul(threes)
+            .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+        Ok(product.into())
+    }
+
+    fn part_two(nums: &Vec<u64>) -> Result<Answer, AocError> {
+        let diffs: Vec<u64> = nums
+            .iter()
+            .zip(nums[1..].iter())
+            .map(|(x, next)| next - x)
+            .collect();
+
+        // Here's how this works. First, notice that if we have two adapters with a
+        // gap of three jolts, neither of those adapters can never be removed from
+        // the sequence. If we did, we'd create a different of > 3 jolts between the
+        // adapters on either side of the hole we make, which is invalid. Thus, we
+        // can look for runs of one-difference adapters and consider each of those
+        // in isolation. (See comment below for why we're ignoring 2-difference
+        // adapters). These runs of consecutive adapters can then be treated in
+        // isolation, and the total number of combinations is the product of the
+        // combinations we can create frmo each run. Furthermore, each run of
+        // consecutive adapters of the same length is identical - four consecutive
+        // adapters always add the same number of combinations to our product no
+        // matter where in the whole sequence that run appears. Thus, ultimately
+        // all we need to do is find the _lengths_ of all the runs of consecutive
+        // adapters, map those lengths to the number of combinations they produce,
+        // and then multiply those combinations together.
+
+        let runs_of_ones: Vec<u64> = diffs
+            .iter()
+            .try_fold(vec![0], |mut runs, x| {
+                let len = runs.len();
+                match x {
+                    1 => runs[len - 1] += 1,
+                    3 => runs.push(0),
+                    // A difference of 2 between adapters isn't invalid according to
+                    // the problem description, but in looking at the examples and
+                    // input I discovered that it never appears. The algorithm I'm
+                    // using is a lot simpler for the case where there are no twos,
+                    // so that's what I've implemented.
+                    2 => return Err(AocError::Misc("Found a 2, need a better algorithm".to_string())),
+                    _ => return Err(AocError::Misc("Found > 3, invalid input".to_string()))
+                };
+                Ok(runs)
+            })?;
+
+        let combinations = runs_of_ones
+            .iter()
+            .try_fold(1u64, |prod, len| {
+                // This is a precomputed table of how many combinations a run of
+                // consecutive adapters produces. My input never goes above four
+                // in a row, so that's where I stopped here. I've made a couple
+                // steps towards working out the general formula but don't have
+                // enough time to spend on it.
+                let factor = match len {
+                    0 => 1,
+                    1 => 1,
+                    2 => 2,
+                    3 => 4,
+                    4 => 7,
+                    _ => return Err(AocError::Misc("Long run, need a better algorithm".to_string()))
+                };
+
+                prod.checked_mul(factor).ok_or_else(|| AocError::Misc("Overflow".to_string()))
+            })?;
+
+        Ok(combinations.into())
+    }
+
+    // Part two only handles gaps of one and three jolts, with at most four
+    // one jolt gaps in a row.
+    fn validate(input: &str) -> Option<Vec<AocError>> {
+        let mut problems = Vec::new();
+
+        let mut adapters = Vec::new();
+        for line in input.lines() {
+            match line.parse::<u64>() {
+                Ok(jolts) => adapters.push((jolts, line)),
+                Err(_) => problems.push(AocError::parse("Not a number", input, line))
+            }
+        }
+        adapters.sort_unstable();
+
+        let mut previous = 0;
+        let mut run = 0;
+        for (jolts, line) in adapters {
+            let gap = jolts - previous;
+            match gap {
+                1 => {
+                    run += 1;
+                    if run == 5 {
+                        problems.push(AocError::parse("More than four one jolt gaps in a row", input, line));
+                    }
+                },
+                3 => {},
+                0 => problems.push(AocError::parse("Same joltage as the one before", input, line)),
+                _ => problems.push(AocError::parse(&format!("{} jolt gap from {}", gap, previous), input, line))
+            }
+            if gap != 1 {
+                run = 0;
+            }
+            previous = jolts;
+        }
+
+        Some(problems)
+    }
+
+    // Size is how many adapters. The gaps are all one or three jolts, with no
+    // more than four ones in a row, which is all part two handles. Only a few
+    // of the runs are long enough to multiply the arrangements, any more and
+    // part two's answer wouldn't fit in a u64.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // 7^20 is about 8 * 10^16.
+        let mut long_runs = 20;
+
+        let size = size.max(1);
+        let mut adapters = Vec::with_capacity(size + 4);
+        let mut jolts = 0;
+        while adapters.len() < size {
+            let run = if long_runs > 0 && rng.chance(0.3) {
+                long_runs -= 1;
+                rng.between(2, 4)
+            } else {
+                rng.between(0, 1)
+            };
+
+            for _ in 0..run {
+                jolts += 1;
+                adapters.push(jolts);
+            }
+            jolts += 3;
+            adapters.push(jolts);
+        }
+        adapters.truncate(size);
+        rng.shuffle(&mut adapters);
+
+        let lines: Vec<String> = adapters.iter().map(u64::to_string).collect();
+        Some(lines.join("\n"))
+    }
+
+    // Gaps of two jolts, and runs of more than four one jolt gaps, which
+    // part two's table doesn't cover. They come in stretches of up to eight
+    // adapters between the usual three jolt gaps, with few enough stretches
+    // that the number of arrangements still fits in a u64.
+    fn generate_tricky(rng: &mut Rng, size: usize) -> Option<String> {
+        // A stretch has at most 81 arrangements, 81^8 is about 2 * 10^15.
+        let mut stretches = 8;
+
+        let size = size.max(1);
+        let mut adapters = Vec::with_capacity(size + 8);
+        let mut jolts = 0;
+        while adapters.len() < size {
+            if stretches > 0 && rng.chance(0.3) {
+                stretches -= 1;
+                for _ in 0..rng.between(5, 8) {
+                    jolts += rng.between(1, 2);
+                    adapters.push(jolts);
+                }
+            }
+            jolts += 3;
+            adapters.push(jolts);
+        }
+        adapters.truncate(size);
+        rng.shuffle(&mut adapters);
+
+        let lines: Vec<String> = adapters.iter().map(u64::to_string).collect();
+        Some(lines.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let chain = reference_chain(input)?;
+        let gaps = |size| chain.windows(2).filter(|pair| pair[1] - pair[0] == size).count();
+
+        Ok((gaps(1) * gaps(3)).into())
+    }
+
+    // Counts the ways to reach each adapter from the ones up to three jolts
+    // below it, whatever the gaps are.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let chain = reference_chain(input)?;
+        let mut ways = vec![0u64; chain.len()];
+        ways[0] = 1;
+        for i in 1..chain.len() {
+            for j in 0..i {
+                if chain[i] - chain[j] <= 3 {
+                    ways[i] = ways[i]
+                        .checked_add(ways[j])
+                        .ok_or_else(|| AocError::Misc("Overflow".to_string()))?;
+                }
+            }
+        }
+
+        Ok(ways[chain.len() - 1].into())
+    }
+}
+
+const EXAMPLE: &str = "
+16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+const LARGER_EXAMPLE: &str = "
+28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "35"),
+    Example::new(Part::One, LARGER_EXAMPLE, "220"),
+    Example::new(Part::Two, EXAMPLE, "8"),
+    Example::new(Part::Two, LARGER_EXAMPLE, "19208")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    #[test]
+    fn validator_finds_every_problem() {
+        let problems = Puzzle::validate("1\n3\n4\n4\n8").unwrap();
+        let messages: Vec<String> = problems.iter().map(|problem| problem.to_string()).collect();
+        assert_eq!(messages, vec![
+            "Parse error at line 2, column 1: 2 jolt gap from 1",
+            "Parse error at line 4, column 1: Same joltage as the one before",
+            "Parse error at line 5, column 1: 4 jolt gap from 4"
+        ]);
+    }
+
+    // The device is 3 above the biggest adapter, which used to overflow.
+    #[test]
+    fn huge_adapters_are_errors() {
+        assert!(solve_part_one::<Puzzle>("18446744073709551615").is_err());
+    }
+
+    // Tens of thousands of each gap multiplied past an i32.
+    #[test]
+    fn stress_sized_inputs() {
+        let input = Puzzle::generate(&mut Rng::new(0), 100_000).unwrap();
+        let reference = Puzzle::reference_one(&input).unwrap();
+        assert_eq!(solve_part_one::<Puzzle>(&input).unwrap(), reference);
+    }
+}
\ No newline at end of file
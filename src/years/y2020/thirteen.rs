@@ -0,0 +1,381 @@
+use std::convert::TryFrom;
+use std::num::ParseIntError;
+use prime_tools::is_u64_prime;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Shuttle Search";
+
+pub struct Notes {
+    pub now: u64,
+    // The buses in schedule order, None for the x's.
+    pub shuttles: Vec<Option<u64>>
+}
+
+fn overflow() -> AocError {
+    AocError::Misc("Overflow".to_string())
+}
+
+// is_u64_prime works by trial division, which takes seconds for each prime
+// near u64::MAX (and overflows right by it) but only microseconds for ones
+// under 2^32. Real buses are a few hundred.
+const MAX_BUS_ID: u64 = 1 << 32;
+
+// The sieve below takes up to as many tries per bus as the bus's ID. Real
+// inputs need a few thousand in all, made up ones with huge IDs could keep it
+// going for hours.
+const MAX_SIEVE_TRIES: u64 = 10_000_000;
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// What a has to be multiplied by to leave 1 over when divided by m, from the
+// extended Euclidean algorithm. a and m can't have any factors in common.
+fn inverse(a: u128, m: u128) -> u128 {
+    let (mut r, mut next_r) = (a as i128, m as i128);
+    let (mut s, mut next_s) = (1i128, 0i128);
+    while next_r != 0 {
+        let quotient = r / next_r;
+        let (new_r, new_s) = (r - quotient * next_r, s - quotient * next_s);
+        r = next_r;
+        s = next_s;
+        next_r = new_r;
+        next_s = new_s;
+    }
+    s.rem_euclid(m as i128) as u128
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Notes;
+
+    fn parse(input: &str) -> Result<Notes, AocError> {
+        let (now, shuttles) = input
+            .split_once("\n")
+            .ok_or_else(|| AocError::Misc("No newline in input".to_string()))?;
+
+        let shuttles = shuttles
+            .split(',')
+            .map(|s| if s == "x" { Ok(None) } else { s.parse().map(Some) })
+            .collect::<Result<Vec<Option<u64>>, ParseIntError>>()?;
+        if shuttles.contains(&Some(0)) {
+            return Err(AocError::Misc("Bus IDs can't be 0".to_string()));
+        }
+        if shuttles.iter().flatten().any(|id| *id > MAX_BUS_ID) {
+            return Err(AocError::Misc("Bus ID too big".to_string()));
+        }
+
+        Ok(Notes { now: now.parse()?, shuttles })
+    }
+
+    fn part_one(notes: &Notes) -> Result<Answer, AocError> {
+        let now = notes.now as f64;
+
+        let (wait, first_shuttle) = notes.shuttles
+            .iter()
+            .flatten()
+            .map(|s| {
+                let s = *s as f64;
+                let arrival = (now / s).ceil() * s;
+                let wait = arrival - now;
+                (wait as usize, s)
+            })
+            .min_by_key(|(wait, _)| *wait)
+            .ok_or_else(|| AocError::Misc("No buses".to_string()))?;
+
+        Ok(wait.checked_mul(first_shuttle as usize).ok_or_else(overflow)?.into())
+    }
+
+    fn part_two(notes: &Notes) -> Result<Answer, AocError> {
+        let mut congruences: Vec<(u64, u64)> = Vec::new();
+        for (remainder, divisor) in notes.shuttles.iter().enumerate() {
+            if let Some(divisor) = *divisor {
+                // If we want a shuttle S to depart at time T, where T > S,
+                // that's equivalent to S departing at T - S, since it'll come
+                // back around. The modulo here generalizes that to all Ts.
+                let remainder = (remainder as u64) % divisor;
+                // I'll be blunt, I'm not certain why we need this subtraction.
+                // Just from reading the problem it seems like we should be tying
+                // to solve a system of congruence relations where each shuttle
+                // is a divisor and the offsets from the first one where we want
+                // them to arrive are the remainders, but just doing that gives
+                // us the wrong answer. Instead we want the remainers to be
+                // divisor - offset? Like I said, not sure why this is what we
+                // want, but it makes the math work out.
+                let remainder = (divisor - remainder) % divisor;
+                congruences.push((remainder, divisor));
+            }
+        }
+
+        // The following algorithm is taken from https://en.wikipedia.org/wiki/Chinese_remainder_theorem#Computation
+        // and is one of several ways of solving a system of congruence relations.
+        // These all rely on the divisors in the congruences all being pairwise
+        // coprime. The input happens to always be generated so that the divisors
+        // are all prime, which is both a stronger condition and easier to verify.
+        for (_, divisor) in &congruences {
+            if !is_u64_prime(*divisor) {
+                return Err(AocError::Misc("Non-prime divisor".to_string()));
+            }
+        }
+
+        // This is the sieving method from the wiki page above. As noted there
+        // this isn't the fastest, but it is plenty fast for this input, and my
+        // eyes are glazing over reading the wikis explanation for the faster
+        // number theory one.
+
+        congruences.sort_unstable_by_key(|(_, divisor)| *divisor);
+        congruences.reverse();
+        // The same prime twice isn't coprime with itself.
+        if congruences.windows(2).any(|pair| pair[0].1 == pair[1].1) {
+            return Err(AocError::Misc("Repeated bus ID".to_string()));
+        }
+
+        let mut answer = congruences
+            .first()
+            .ok_or_else(|| AocError::Misc("No buses".to_string()))?
+            .0;
+        let mut step = 1u64;
+        let mut tries = 0;
+        for i in 0..(congruences.len() - 1) {
+            step = step.checked_mul(congruences[i].1).ok_or_else(overflow)?;
+            let (next_remainder, next_divisor) = congruences[i + 1];
+
+            while answer % next_divisor != next_remainder {
+                tries += 1;
+                if tries > MAX_SIEVE_TRIES {
+                    return Err(AocError::Misc("Bus IDs too big to sieve".to_string()));
+                }
+                answer = answer.checked_add(step).ok_or_else(overflow)?;
+            }
+        }
+
+        Ok(answer.into())
+    }
+
+    // Part two needs every bus ID to be prime, see above.
+    fn validate(input: &str) -> Option<Vec<AocError>> {
+        let mut lines = input.lines();
+        let mut problems = Vec::new();
+
+        if let Some(now) = lines.next() {
+            if now.parse::<u64>().is_err() {
+                problems.push(AocError::parse("Not a number", input, now));
+            }
+        }
+
+        let shuttles = match lines.next() {
+            Some(shuttles) => shuttles,
+            None => {
+                problems.push(AocError::Misc("No bus IDs".to_string()));
+                return Some(problems);
+            }
+        };
+
+        for id in shuttles.split(',').filter(|id| *id != "x") {
+            match id.parse::<u64>() {
+                Ok(id_number) if id_number > MAX_BUS_ID => {
+                    problems.push(AocError::parse("Bus ID too big", input, id));
+                },
+                Ok(id_number) if !is_u64_prime(id_number) => {
+                    problems.push(AocError::parse("Bus ID isn't prime", input, id));
+                },
+                Ok(_) => {},
+                Err(_) => problems.push(AocError::parse("Not a bus ID", input, id))
+            }
+        }
+
+        if let Some(extra) = lines.find(|line| !line.trim().is_empty()) {
+            problems.push(AocError::parse("Expected only two lines", input, extra));
+        }
+
+        Some(problems)
+    }
+
+    // Size is how many places there are in the bus list, most of them x like
+    // the real one. The buses are different primes, and there are only as
+    // many as keep their product, which part two's answer is below, under
+    // 10^18.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let slots = size.max(1);
+        let mut primes: Vec<u64> = (7..1000).filter(|n| is_u64_prime(*n)).collect();
+        rng.shuffle(&mut primes);
+
+        let mut buses = Vec::new();
+        let mut product = 1u64;
+        for prime in primes {
+            let too_big = product.checked_mul(prime).is_none_or(|product| product > 1_000_000_000_000_000_000);
+            if buses.len() == slots.min(9) || too_big {
+                break;
+            }
+            product *= prime;
+            buses.push(prime);
+        }
+
+        // The first bus is always first in the list.
+        let mut schedule = vec!["x".to_string(); slots];
+        let mut places: Vec<usize> = (1..slots).collect();
+        rng.shuffle(&mut places);
+        for (bus, place) in buses.iter().zip(std::iter::once(0).chain(places)) {
+            schedule[place] = bus.to_string();
+        }
+
+        Some(format!("{}\n{}", rng.between(100_000, 1_000_000), schedule.join(",")))
+    }
+
+    // Buses that share factors, so part two can't use its sieve. Each place
+    // in the list gets a bus dividing some made up answer plus the place,
+    // which keeps an answer for part two to find, at or before that one.
+    fn generate_tricky(rng: &mut Rng, size: usize) -> Option<String> {
+        let slots = size.max(1);
+        let first = rng.between(4, 999);
+        let answer = first * rng.between(1_000, 1_000_000);
+
+        let mut schedule = vec![first.to_string()];
+        let mut buses = 1;
+        for place in 1..slots as u64 {
+            let divisors: Vec<u64> = (2..1000).filter(|d| (answer + place).is_multiple_of(*d)).collect();
+            if buses < 9 && !divisors.is_empty() && rng.chance(0.3) {
+                schedule.push(rng.choose(&divisors).to_string());
+                buses += 1;
+            } else {
+                schedule.push("x".to_string());
+            }
+        }
+
+        Some(format!("{}\n{}", rng.between(100_000, 1_000_000), schedule.join(",")))
+    }
+
+    // Waits a minute at a time until a bus turns up.
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let notes = Puzzle::parse(input)?;
+        let buses: Vec<u64> = notes.shuttles.iter().flatten().copied().collect();
+        if buses.is_empty() {
+            return Err(AocError::Misc("No buses".to_string()));
+        }
+
+        let mut time = notes.now;
+        loop {
+            if let Some(bus) = buses.iter().find(|bus| time.is_multiple_of(**bus)) {
+                return Ok(((time - notes.now).checked_mul(*bus).ok_or_else(overflow)?).into());
+            }
+            time = time.checked_add(1).ok_or_else(overflow)?;
+        }
+    }
+
+    // Goes through the buses keeping the earliest time that suits all of them
+    // so far, and how often that comes round again. For each bus it works out
+    // how many of those rounds to wait with the extended Euclidean algorithm,
+    // which copes with buses that share factors as long as some time suits
+    // them all.
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let notes = Puzzle::parse(input)?;
+        if notes.shuttles.iter().all(Option::is_none) {
+            return Err(AocError::Misc("No buses".to_string()));
+        }
+
+        let mut time = 0u128;
+        let mut period = 1u128;
+        for (offset, bus) in notes.shuttles.iter().enumerate() {
+            let bus = match bus {
+                Some(bus) => *bus as u128,
+                None => continue
+            };
+
+            // Waiting another k rounds has to make up the shortfall, modulo
+            // the bus.
+            let shortfall = (bus - (time % bus + offset as u128 % bus) % bus) % bus;
+            let common = gcd(period, bus);
+            if !shortfall.is_multiple_of(common) {
+                return Err(AocError::Misc("No time works for every bus".to_string()));
+            }
+            let step = bus / common;
+            let rounds = shortfall / common * inverse(period / common % step, step) % step;
+
+            time = period
+                .checked_mul(rounds)
+                .and_then(|wait| time.checked_add(wait))
+                .ok_or_else(overflow)?;
+            period = period.checked_mul(step).ok_or_else(overflow)?;
+        }
+
+        Ok(u64::try_from(time).map_err(|_| overflow())?.into())
+    }
+}
+
+const EXAMPLE: &str = "
+939
+7,13,x,x,59,x,31,19";
+
+// Part two's extra examples only give the bus list, the first line is just
+// there to make them look like an input.
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "295"),
+    Example::new(Part::Two, EXAMPLE, "1068781"),
+    Example::new(Part::Two, "0\n17,x,13,19", "3417"),
+    Example::new(Part::Two, "0\n67,7,59,61", "754018"),
+    Example::new(Part::Two, "0\n67,x,7,59,61", "779210"),
+    Example::new(Part::Two, "0\n67,7,x,59,61", "1261476"),
+    Example::new(Part::Two, "0\n1789,37,47,1889", "1202161486")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::{solve_part_one, solve_part_two};
+
+    // Part two's reference doesn't need the buses to be prime, just for
+    // some time to suit them all.
+    #[test]
+    fn reference_copes_with_shared_factors() {
+        assert_eq!(Puzzle::reference_two("0\n4,x,6").unwrap(), Answer::Int(4));
+        assert_eq!(Puzzle::reference_two("0\n6,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,10").unwrap(), Answer::Int(24));
+        assert!(Puzzle::reference_two("0\n4,6").is_err());
+    }
+
+    #[test]
+    fn validator_wants_prime_bus_ids() {
+        assert_eq!(Puzzle::validate("939\n7,13,x,15").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn huge_bus_ids_are_errors() {
+        assert!(Puzzle::parse("1\n18446744073709551557").is_err());
+
+        // Both prime, but sieving the second would take a billion tries.
+        let notes = Puzzle::parse("1\n1000000007,999999937").unwrap();
+        assert!(Puzzle::part_two(&notes).is_err());
+    }
+
+    #[test]
+    fn big_bus_ids() {
+        let notes = Puzzle::parse("1\n4294967291,61").unwrap();
+        assert_eq!(Puzzle::part_two(&notes).unwrap(), Answer::from(146028887894u64));
+    }
+
+    // Each of these took seconds to check was prime, in part two and in the
+    // validator, so a list of them could keep either going for hours.
+    #[test]
+    fn huge_prime_bus_ids_are_errors() {
+        let input = "1\n9223372036854775783,9223372036854775643";
+        assert!(solve_part_two::<Puzzle>(input).is_err());
+        let problems = Puzzle::validate(input).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert!(problems.iter().all(|problem| problem.to_string().contains("Bus ID too big")));
+    }
+
+    // Part one used to unwrap the earliest bus.
+    #[test]
+    fn no_buses_is_an_error() {
+        assert!(solve_part_one::<Puzzle>("939\nx").is_err());
+    }
+
+    // The same bus twice used to sieve forever.
+    #[test]
+    fn repeated_buses_are_errors() {
+        assert!(solve_part_two::<Puzzle>("939\n7,7").is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Toboggan Trajectory";
+
+pub struct TreeMap<'a> {
+    trees: &'a str,
+    height: usize,
+    tile_width: usize
+}
+
+impl TreeMap<'_> {
+    pub fn new(text: &str) -> TreeMap<'_> {
+        let height = text.lines().count();
+        // Input is known to be rectangular so we can get away with only
+        // counting one line.
+        let width = text.chars().take_while(|c| c != &'\n').count();
+
+        TreeMap {
+            trees: text,
+            height,
+            tile_width: width
+        }
+    }
+
+    pub fn tree_at(&self, row: usize, col: usize) -> bool {
+        if row >= self.height {
+            // It's convenient for one of the part two seconds to go down
+            // past the end of the map.
+            return false
+        }
+
+        let col = col % self.tile_width;
+
+        // +1 here to account for newline chars
+        let i = row * (self.tile_width + 1) + col;
+        &self.trees[i..i + 1] == "#"
+    }
+
+    pub fn trees_at_slope(&self, drow: usize, dcol: usize) -> usize {
+        (0..self.height)
+            .map(|i| (i * drow, i * dcol))
+            .filter(|(row, col)| self.tree_at(*row, *col))
+            .count()
+    }
+}
+
+// Walks the map a row at a time from the top left, for the references.
+fn trees_on_slope(rows: &[Vec<char>], right: usize, down: usize) -> usize {
+    let mut trees = 0;
+    let (mut row, mut col) = (0, 0);
+    while row < rows.len() {
+        if rows[row][col % rows[row].len()] == '#' {
+            trees += 1;
+        }
+        row += down;
+        col += right;
+    }
+    trees
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = TreeMap<'a>;
+
+    fn parse(input: &str) -> Result<TreeMap<'_>, AocError> {
+        // TreeMap indexes straight into the text, so anything that isn't a
+        // rectangle of single byte characters would land it in the wrong
+        // place, or outside the text altogether.
+        // Splitting on \n rather than using lines() so a \r\n gets caught.
+        let rows = input.strip_suffix('\n').unwrap_or(input).split('\n');
+        let width = input.lines().next().map_or(0, str::len);
+        for line in rows {
+            if line.len() != width || width == 0 {
+                return Err(AocError::parse("Every line has to be as wide as the first", input, line));
+            }
+            if let Some(i) = line.find(|c| c != '.' && c != '#') {
+                return Err(AocError::parse("Only . and # are allowed", input, &line[i..]));
+            }
+        }
+
+        Ok(TreeMap::new(input))
+    }
+
+    fn part_one(tree_map: &TreeMap<'_>) -> Result<Answer, AocError> {
+        Ok(tree_map.trees_at_slope(1, 3).into())
+    }
+
+    fn part_two(tree_map: &TreeMap<'_>) -> Result<Answer, AocError> {
+        let product =
+            tree_map.trees_at_slope(1, 1) *
+            tree_map.trees_at_slope(1, 3) *
+            tree_map.trees_at_slope(1, 5) *
+            tree_map.trees_at_slope(1, 7) *
+            tree_map.trees_at_slope(2, 1);
+
+        Ok(product.into())
+    }
+
+    // TreeMap works out positions from the first line's width, so every line
+    // has to be as wide as that one.
+    fn validate(input: &str) -> Option<Vec<AocError>> {
+        let width = input.lines().next().map_or(0, str::len);
+        let mut problems = Vec::new();
+
+        for line in input.lines() {
+            if let Some(i) = line.find(|c| c != '.' && c != '#') {
+                let c = line[i..].chars().next().unwrap();
+                problems.push(AocError::parse("Not a tree or open ground", input, &line[i..i + c.len_utf8()]));
+            } else if line.len() > width {
+                problems.push(AocError::parse("Line is wider than the first", input, &line[width..]));
+            } else if line.len() < width {
+                problems.push(AocError::parse("Line is narrower than the first", input, line));
+            }
+        }
+
+        Some(problems)
+    }
+
+    // Size is how many rows, the map stays 31 wide like the real one.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let rows: Vec<String> = (0..size.max(1))
+            .map(|_| (0..31).map(|_| if rng.chance(0.25) { '#' } else { '.' }).collect())
+            .collect();
+
+        Some(rows.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        Ok(trees_on_slope(&rows, 3, 1).into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let product: usize = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .iter()
+            .map(|(right, down)| trees_on_slope(&rows, *right, *down))
+            .product();
+
+        Ok(product.into())
+    }
+}
+
+const EXAMPLE: &str = "
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "7"),
+    Example::new(Part::Two, EXAMPLE, "336")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_one;
+
+    // A short row used to send the index past the end of the text.
+    #[test]
+    fn ragged_rows_are_errors() {
+        assert!(solve_part_one::<Puzzle>("..\n.").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,302 @@
+use std::str::FromStr;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Rain Risk";
+
+#[derive(Debug)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize
+}
+
+// Staying clear of isize::MIN as well as overflow means any coordinate can be
+// negated or have its abs taken, which turning and manhattan both rely on.
+fn in_range(n: Option<isize>) -> Result<isize, AocError> {
+    n.filter(|n| *n != isize::MIN)
+        .ok_or_else(|| AocError::Misc("Ferry went too far".to_string()))
+}
+
+impl Vec2 {
+    fn moved(&self, by: &Vec2, times: isize) -> Result<Vec2, AocError> {
+        Ok(Vec2 {
+            x: in_range(by.x.checked_mul(times).and_then(|x| self.x.checked_add(x)))?,
+            y: in_range(by.y.checked_mul(times).and_then(|y| self.y.checked_add(y)))?
+        })
+    }
+}
+
+// We only ever turn in 90 degree increments.
+// Normalize left and right turns to always be right.
+#[derive(Debug, Copy, Clone)]
+pub enum Degrees {
+    Zero = 0,
+    Ninety = 1,
+    OneEighty = 2,
+    TwoSeventy = 3
+}
+
+impl Degrees {
+    pub fn from_args(right: bool, mut n: isize) -> Result<Degrees, AocError> {
+        if !right {
+            n = 360 - n;
+        }
+
+        match n {
+            0 => Ok(Degrees::Zero),
+            90 => Ok(Degrees::Ninety),
+            180 => Ok(Degrees::OneEighty),
+            270 => Ok(Degrees::TwoSeventy),
+            _ => Err(AocError::Misc("Invalid turn amount".to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Heading {
+    North = 0,
+    South = 2,
+    East = 1,
+    West = 3
+}
+
+const CLOCKWISE: [Heading; 4] =
+    [Heading::North, Heading::East, Heading::South, Heading::West];
+impl Heading {
+    pub fn turn(&self, deg: &Degrees) -> Heading {
+        CLOCKWISE[(*self as usize + *deg as usize) % CLOCKWISE.len()]
+    }
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    Shift(Vec2),
+    Turn(Degrees),
+    // Forward moves can only ever be positive, but this saves us a bunch of
+    // casting.
+    Forward(isize)
+}
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (code, amount_str) = match (s.get(0..1), s.get(1..)) {
+            (Some(code), Some(amount_str)) => (code, amount_str),
+            _ => return Err(AocError::parse("Invalid instruction", s, s))
+        };
+        // Amounts are never negative, and keeping them to a u32 means
+        // negating them for S and W can't overflow.
+        let amount = amount_str
+            .parse::<u32>()
+            .map_err(|_| AocError::parse("Bad number", s, amount_str))? as isize;
+        let turn = |right| Degrees::from_args(right, amount)
+            .map_err(|_| AocError::parse("Invalid turn amount", s, amount_str));
+
+        match code {
+            "N" => Ok(Instruction::Shift(Vec2{ x: 0, y: amount })),
+            "S" => Ok(Instruction::Shift(Vec2{ x: 0, y: -amount })),
+            "E" => Ok(Instruction::Shift(Vec2{ x: amount, y: 0 })),
+            "W" => Ok(Instruction::Shift(Vec2{ x: -amount, y: 0 })),
+            "L" => Ok(Instruction::Turn(turn(false)?)),
+            "R" => Ok(Instruction::Turn(turn(true)?)),
+            "F" => Ok(Instruction::Forward(amount)),
+            _ => Err(AocError::parse("Invalid instruction code", s, code))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Ferry {
+    pos: Vec2,
+    heading: Heading,
+    waypoint: Vec2
+}
+
+impl Ferry {
+    fn new() -> Ferry {
+        Ferry {
+            pos: Vec2{ x: 0, y: 0 },
+            heading: Heading::East,
+            waypoint: Vec2{ x: 10, y: 1 }
+        }
+    }
+
+    fn follow_instruction_p1(&mut self, instruction: &Instruction) -> Result<(), AocError> {
+        match instruction {
+            Instruction::Shift(offset) => {
+                self.pos = self.pos.moved(offset, 1)?;
+            },
+            Instruction::Turn(deg) => {
+                self.heading = self.heading.turn(deg);
+            },
+            Instruction::Forward(distance) => {
+                let direction = match self.heading {
+                    Heading::North => Vec2{ x: 0, y: 1 },
+                    Heading::South => Vec2{ x: 0, y: -1 },
+                    Heading::East => Vec2{ x: 1, y: 0 },
+                    Heading::West => Vec2{ x: -1, y: 0 }
+                };
+                self.pos = self.pos.moved(&direction, *distance)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn follow_instruction_p2(&mut self, instruction: &Instruction) -> Result<(), AocError> {
+        match instruction {
+            Instruction::Shift(offset) => {
+                self.waypoint = self.waypoint.moved(offset, 1)?;
+            },
+            Instruction::Turn(deg) => {
+                match deg {
+                    Degrees::Zero => {},
+                    Degrees::Ninety => {
+                        let old_x = self.waypoint.x;
+                        self.waypoint.x = self.waypoint.y;
+                        self.waypoint.y = -old_x;
+                    },
+                    Degrees::OneEighty => {
+                        self.waypoint.x = -self.waypoint.x;
+                        self.waypoint.y = -self.waypoint.y;
+                    },
+                    Degrees::TwoSeventy => {
+                        let old_x = self.waypoint.x;
+                        self.waypoint.x = -self.waypoint.y;
+                        self.waypoint.y = old_x;
+                    }
+                }
+            },
+            Instruction::Forward(times) => {
+                self.pos = self.pos.moved(&self.waypoint, *times)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn manhattan(&self) -> Result<isize, AocError> {
+        in_range(self.pos.x.abs().checked_add(self.pos.y.abs()))
+    }
+}
+
+// Sails the instructions with the heading or waypoint as a plain vector,
+// turning a quarter at a time. With a waypoint the moves shift it rather
+// than the ship.
+fn reference_sail(input: &str, waypoint: Option<(i64, i64)>) -> Result<i64, AocError> {
+    let mut ship = (0i64, 0i64);
+    let mut heading = waypoint.unwrap_or((1, 0));
+
+    for line in input.lines() {
+        let mut chars = line.chars();
+        let action = chars.next().ok_or_else(|| AocError::parse("Invalid instruction", input, line))?;
+        let amount: i64 = chars.as_str().parse()?;
+
+        let shift = match action {
+            'N' => (0, amount),
+            'S' => (0, -amount),
+            'E' => (amount, 0),
+            'W' => (-amount, 0),
+            'L' | 'R' => {
+                if amount % 90 != 0 {
+                    return Err(AocError::parse("Invalid turn amount", input, line));
+                }
+                for _ in 0..(amount / 90).rem_euclid(4) {
+                    heading = if action == 'L' { (-heading.1, heading.0) } else { (heading.1, -heading.0) };
+                }
+                continue;
+            },
+            'F' => {
+                ship = (ship.0 + heading.0 * amount, ship.1 + heading.1 * amount);
+                continue;
+            },
+            _ => return Err(AocError::parse("Invalid instruction code", input, line))
+        };
+
+        if waypoint.is_some() {
+            heading = (heading.0 + shift.0, heading.1 + shift.1);
+        } else {
+            ship = (ship.0 + shift.0, ship.1 + shift.1);
+        }
+    }
+
+    Ok(ship.0.abs() + ship.1.abs())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, AocError> {
+        input
+            .lines()
+            .map(|line| line.parse().map_err(|error: AocError| error.within(input, line)))
+            .collect()
+    }
+
+    fn part_one(instructions: &Vec<Instruction>) -> Result<Answer, AocError> {
+        let mut ferry = Ferry::new();
+        for instruction in instructions {
+            ferry.follow_instruction_p1(instruction)?;
+        }
+
+        Ok(ferry.manhattan()?.into())
+    }
+
+    fn part_two(instructions: &Vec<Instruction>) -> Result<Answer, AocError> {
+        let mut ferry = Ferry::new();
+        for instruction in instructions {
+            ferry.follow_instruction_p2(instruction)?;
+        }
+
+        Ok(ferry.manhattan()?.into())
+    }
+
+    // Size is how many instructions, with moves about as big as the real
+    // ones so the ferry stays well within an isize.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let lines: Vec<String> = (0..size)
+            .map(|_| match rng.below(4) {
+                0 => format!("{}{}", rng.choose(&['N', 'S', 'E', 'W']), rng.between(1, 5)),
+                1 => format!("{}{}", rng.choose(&['L', 'R']), rng.choose(&[90, 180, 270])),
+                _ => format!("F{}", rng.between(1, 100))
+            })
+            .collect();
+
+        Some(lines.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_sail(input, None)?.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        Ok(reference_sail(input, Some((10, 1)))?.into())
+    }
+}
+
+const EXAMPLE: &str = "
+F10
+N3
+F7
+R90
+F11";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "25"),
+    Example::new(Part::Two, EXAMPLE, "286")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_two;
+
+    // Moves this big used to overflow the position.
+    #[test]
+    fn huge_moves_are_errors() {
+        assert!(solve_part_two::<Puzzle>("N4294967295\nF4294967295").is_err());
+    }
+}
\ No newline at end of file
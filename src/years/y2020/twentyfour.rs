@@ -0,0 +1,25 @@
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::years::{Example, Solution};
+
+pub const NAME: &str = "Unknown";
+
+pub const EXAMPLES: &[Example] = &[];
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = ();
+
+    fn parse(_input: &str) -> Result<(), AocError> {
+        Ok(())
+    }
+
+    fn part_one(_parsed: &()) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_two(_parsed: &()) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+}
\ No newline at end of file
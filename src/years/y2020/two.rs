@@ -0,0 +1,207 @@
+use std::str::FromStr;
+use regex::Regex;
+use crate::aoc_error::AocError;
+use crate::answer::Answer;
+use crate::rng::Rng;
+use crate::years::{Example, Part, Solution};
+
+pub const NAME: &str = "Password Philosophy";
+
+pub struct Rule {
+    pub letter: char,
+    pub x: usize,
+    pub y: usize
+}
+
+impl FromStr for Rule {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RULE_PATTERN: Regex =
+                Regex::new("([0-9]+)-([0-9]+) ([a-z])").unwrap();
+        }
+
+        // Using ok_or instead of ok_or_else can be a significant part of the
+        // runtime for a super short program like this. With ok_or_else part
+        // two runs in about 600us, with ok_or it's about 1ms. Four string
+        // allocations in a loop nearly doubles the time.
+
+        let bad_rule = || AocError::parse("Bad rule string", s, s);
+
+        let captures = RULE_PATTERN
+            .captures(s)
+            .ok_or_else(bad_rule)?;
+
+        let x = captures.get(1).ok_or_else(bad_rule)?.as_str();
+        let x = x.parse().map_err(|_| AocError::parse("Bad number", s, x))?;
+
+        let y = captures.get(2).ok_or_else(bad_rule)?.as_str();
+        let y = y.parse().map_err(|_| AocError::parse("Bad number", s, y))?;
+
+        let letter = captures.get(3)
+            .ok_or_else(bad_rule)?
+            .as_str()
+            .chars()
+            .next()
+            // This unwrap is safe because this capture can only match if
+            // there a char there to iterate over.
+            .unwrap();
+
+        Ok(Rule { letter, x, y })
+    }
+}
+
+impl Rule {
+    pub fn validate_sled_rental(&self, password: &str) -> bool {
+        let count = password
+            .chars()
+            .filter(|c| c == &self.letter)
+            .count();
+        // Sled rental interprets x and y as min and max counts of letter.
+        count >= self.x && count <= self.y
+    }
+
+    pub fn validate_toboggan_corporate(&self, password: &str) -> Result<bool, AocError> {
+        // Toboggan Corporate interprets x and y as 1-based indices where
+        // exactly one must match letter.
+        let nth = |i: usize| i.checked_sub(1).and_then(|i| password.chars().nth(i));
+        let first_match = nth(self.x);
+        let second_match = nth(self.y);
+        match (first_match, second_match) {
+            (Some(char1), Some(char2)) =>
+                Ok((char1 == self.letter) ^ (char2 == self.letter)),
+            (_, _) => Err(AocError::Misc("Not enough chars in password".to_string()))
+        }
+    }
+}
+
+fn random_letter(rng: &mut Rng) -> char {
+    (b'a' + rng.between(0, 25) as u8) as char
+}
+
+// The references' own reading of a line, "x-y letter: password".
+fn read_policy(line: &str) -> Result<(usize, usize, char, &str), AocError> {
+    let bad_line = || AocError::parse("Bad password line", line, line);
+    let (rule, password) = line.split_once(": ").ok_or_else(bad_line)?;
+    let (range, letter) = rule.split_once(' ').ok_or_else(bad_line)?;
+    let (x, y) = range.split_once('-').ok_or_else(bad_line)?;
+
+    let mut letters = letter.chars();
+    let letter = match (letters.next(), letters.next()) {
+        (Some(letter), None) => letter,
+        _ => return Err(bad_line())
+    };
+
+    Ok((x.parse()?, y.parse()?, letter, password))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    // Each rule with the password it applies to.
+    type Parsed<'a> = Vec<(Rule, &'a str)>;
+
+    fn parse(input: &str) -> Result<Vec<(Rule, &str)>, AocError> {
+        input
+            .lines()
+            .map(|line| match line.split_once(": ") {
+                Some((rule, password)) => rule
+                    .parse::<Rule>()
+                    .map(|rule| (rule, password))
+                    .map_err(|error| error.within(input, rule)),
+                None => Err(AocError::parse("Bad password line", input, line))
+            })
+            .collect()
+    }
+
+    fn part_one(passwords: &Vec<(Rule, &str)>) -> Result<Answer, AocError> {
+        let count = passwords
+            .iter()
+            .filter(|(rule, password)| rule.validate_sled_rental(password))
+            .count();
+
+        Ok(count.into())
+    }
+
+    fn part_two(passwords: &Vec<(Rule, &str)>) -> Result<Answer, AocError> {
+        let count = passwords
+            .iter()
+            .map(|(rule, password)| rule.validate_toboggan_corporate(password))
+            .collect::<Result<Vec<bool>, AocError>>()?
+            .iter()
+            .filter(|valid| **valid)
+            .count();
+
+        Ok(count.into())
+    }
+
+    // Size is how many passwords. They're always long enough for part two's
+    // positions, and lean towards their rule's letter so that some pass.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let lines: Vec<String> = (0..size)
+            .map(|_| {
+                let x = rng.between(1, 10);
+                let y = rng.between(x + 1, 20);
+                let letter = random_letter(rng);
+                let password: String = (0..rng.between(y, 20))
+                    .map(|_| if rng.chance(0.4) { letter } else { random_letter(rng) })
+                    .collect();
+                format!("{}-{} {}: {}", x, y, letter, password)
+            })
+            .collect();
+
+        Some(lines.join("\n"))
+    }
+
+    fn reference_one(input: &str) -> Result<Answer, AocError> {
+        let mut count = 0;
+        for line in input.lines() {
+            let (min, max, letter, password) = read_policy(line)?;
+            let times = password.chars().filter(|c| *c == letter).count();
+            if min <= times && times <= max {
+                count += 1;
+            }
+        }
+
+        Ok(count.into())
+    }
+
+    fn reference_two(input: &str) -> Result<Answer, AocError> {
+        let mut count = 0;
+        for line in input.lines() {
+            let (first, second, letter, password) = read_policy(line)?;
+            let password: Vec<char> = password.chars().collect();
+            if first == 0 || second == 0 || first > password.len() || second > password.len() {
+                return Err(AocError::Misc("Not enough chars in password".to_string()));
+            }
+            if (password[first - 1] == letter) != (password[second - 1] == letter) {
+                count += 1;
+            }
+        }
+
+        Ok(count.into())
+    }
+}
+
+const EXAMPLE: &str = "
+1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+pub const EXAMPLES: &[Example] = &[
+    Example::new(Part::One, EXAMPLE, "2"),
+    Example::new(Part::Two, EXAMPLE, "1")
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::years::solve_part_two;
+
+    // Position 0 used to underflow.
+    #[test]
+    fn positions_start_at_one() {
+        assert!(solve_part_two::<Puzzle>("0-1 a: abc").is_err());
+    }
+}
\ No newline at end of file